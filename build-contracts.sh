@@ -58,7 +58,8 @@ if [ "${BUILD_COORDINATOR:-1}" = "1" ]; then
 fi
 
 if [ "${BUILD_FACTORY:-1}" = "1" ]; then
-  # Factory embeds coordinator WASM — build coordinator first (BUILD_COORDINATOR=1)
+  # Factory stores coordinator WASM in state — after deploying, upload the
+  # coordinator build with `upload_code` (raw WASM as call input)
   build_contract "factory-contract" "$ROOT/factory-contract"
   mkdir -p "$ROOT/factory-contract/target/near"
   optimize_wasm "factory" \
@@ -5,6 +5,7 @@
 import { Buffer } from 'buffer';
 import { connect, keyStores, KeyPair, Account } from 'near-api-js';
 import { parseSeedPhrase } from 'near-seed-phrase';
+import type { WorkerSubmissionInput } from '@near-shade-coordination/shared';
 
 const NEAR_NETWORK = process.env.NEAR_NETWORK || 'testnet';
 const CONTRACT_ID = process.env.NEXT_PUBLIC_contractId
@@ -152,7 +153,7 @@ export async function localStartCoordination(
  */
 export async function localRecordWorkerSubmissions(
   proposalId: number,
-  submissions: Array<{ worker_id: string; result_hash: string }>
+  submissions: WorkerSubmissionInput[]
 ): Promise<boolean> {
   try {
     await contractCall('record_worker_submissions', {
@@ -338,7 +339,8 @@ export async function localRegisterCoordinator(
   endpointUrl: string,
   minWorkers: number,
   maxWorkers: number,
-  cvmId: string = 'local',
+  cvmId: string,
+  codehash: string,
 ): Promise<boolean> {
   try {
     // Check if already registered
@@ -354,6 +356,7 @@ export async function localRegisterCoordinator(
       coordinator_did: coordinatorDid,
       endpoint_url: endpointUrl,
       cvm_id: cvmId,
+      codehash,
       min_workers: minWorkers,
       max_workers: maxWorkers,
     }, DEPOSIT_0_1_NEAR);
@@ -375,6 +378,7 @@ export async function localRegisterWorkerInRegistry(
   workerDid: string,
   endpointUrl: string,
   cvmId: string,
+  codehash: string,
 ): Promise<boolean> {
   try {
     await localCallRegistry('register_worker', {
@@ -382,6 +386,7 @@ export async function localRegisterWorkerInRegistry(
       worker_did: workerDid,
       endpoint_url: endpointUrl,
       cvm_id: cvmId,
+      codehash,
     }, DEPOSIT_0_1_NEAR);
 
     console.log(`[REGISTRY] Worker registered: ${workerDid} → coordinator ${coordinatorDid}`);
@@ -26,6 +26,12 @@ if (!LOCAL_MODE && !SHADE_AGENT_CONFIGURED) {
   console.warn('WARNING: ShadeClient will not initialize — on-chain coordination disabled');
   console.warn('WARNING: Server will start in degraded mode (health check only)');
 }
+// The registry only lists agents with an approved codehash, so an empty one can never register
+const APP_CODEHASH = process.env.APP_CODEHASH ?? '';
+if (!APP_CODEHASH) {
+  console.error('APP_CODEHASH is not set — it is required to register in the registry');
+  process.exit(1);
+}
 
 const app = new Hono();
 
@@ -148,7 +154,9 @@ async function ensureCoordinatorRegistered(): Promise<void> {
     const maxWorkers = parseInt(process.env.MAX_WORKERS ?? '10');
     const cvmId = process.env.PHALA_CVM_ID ?? 'local';
 
-    const registered = await localRegisterCoordinator(coordinatorDid, endpointUrl, minWorkers, maxWorkers, cvmId);
+    const registered = await localRegisterCoordinator(
+      coordinatorDid, endpointUrl, minWorkers, maxWorkers, cvmId, APP_CODEHASH,
+    );
     if (registered) {
       console.log(`[REGISTRY] Coordinator registered/confirmed: ${coordinatorDid}`);
     } else {
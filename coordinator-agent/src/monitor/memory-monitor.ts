@@ -12,16 +12,22 @@ import type {
   CoordinationRequest,
   WorkerResult,
   TallyResult,
+  SubmissionStatus,
+  WorkerSubmissionInput,
 } from '@near-shade-coordination/shared';
 import crypto from 'crypto';
 import {
   localStartCoordination,
   localCoordinatorResume,
   localRecordWorkerSubmissions,
+  localRegisterWorker,
+  localGetRegisteredWorkers,
+  localViewCall,
 } from '../contract/local-contract';
 import { backupDeliberation, isVaultConfigured } from '../storacha/vault';
 import { backupEnsueTree } from '../storacha/ensue-backup';
 import { archiveCID, logArchivalToNear } from '../filecoin/archiver';
+import { parseProposalOptions, tallyOptions } from './option-tally';
 
 const LOCAL_MODE = process.env.LOCAL_MODE === 'true';
 
@@ -204,7 +210,9 @@ export async function triggerLocalCoordination(taskConfig: string): Promise<Tall
       return null;
     }
 
-    // Step 4: Start coordination on-chain with actual reachable worker count
+    // Step 4: Start coordination on-chain with actual reachable worker count.
+    // Register unknown workers first so the proposal's weight snapshot includes them.
+    await registerMissingWorkers(activeWorkers.map(w => w.worker_did));
     console.log('[LOCAL] Starting on-chain coordination...');
 
     let proposalId: number | null = null;
@@ -239,23 +247,7 @@ export async function triggerLocalCoordination(taskConfig: string): Promise<Tall
       await getEnsueClient().updateMemory(MEMORY_KEYS.COORDINATOR_STATUS, 'recording_submissions');
       console.log('[LOCAL] Recording worker submissions on-chain...');
 
-      const resultKeys = workerDIDs.map(did => getWorkerKeys(did).RESULT);
-      const workerResults = await getEnsueClient().readMultiple(resultKeys);
-      // Only send worker_id + result_hash on-chain (nullifier).
-      // Individual votes stay private in Ensue shared memory.
-      const submissions = resultKeys
-        .map(key => {
-          const resultStr = workerResults[key];
-          if (!resultStr) return null;
-          try {
-            const result = JSON.parse(resultStr);
-            return {
-              worker_id: result.workerId as string,
-              result_hash: crypto.createHash('sha256').update(resultStr).digest('hex'),
-            };
-          } catch { return null; }
-        })
-        .filter((s): s is { worker_id: string; result_hash: string } => s !== null);
+      const submissions = await buildWorkerSubmissions(workerDIDs);
 
       try {
         const recorded = await localRecordWorkerSubmissions(proposalId, submissions);
@@ -313,16 +305,20 @@ export async function triggerLocalCoordination(taskConfig: string): Promise<Tall
     }
 
     // Step 7: Resume contract with on-chain settlement (privacy-preserving)
-    if (proposalId !== null) {
+    if (proposalId !== null && !tally.quorumReached) {
+      console.warn(`[LOCAL] Participation ${tally.participation} is below quorum, proposal #${proposalId} will time out`);
+    } else if (proposalId !== null) {
       await getEnsueClient().updateMemory(MEMORY_KEYS.COORDINATOR_STATUS, 'resuming');
       const onChainResult = JSON.stringify({
         aggregatedValue: tally.aggregatedValue,
         approved: tally.approved,
         rejected: tally.rejected,
+        abstained: tally.abstained,
         decision: tally.decision,
         workerCount: tally.workerCount,
         timestamp: tally.timestamp,
         proposalId,
+        ...tally.optionTally,
       });
       const resultHash = crypto.createHash('sha256').update(onChainResult).digest('hex');
       console.log('[LOCAL] Resuming contract with on-chain result...');
@@ -463,23 +459,7 @@ async function processCoordination(
     await getEnsueClient().updateMemory(MEMORY_KEYS.COORDINATOR_STATUS, 'recording_submissions');
     console.log('Recording worker submissions on-chain...');
 
-    // Only send worker_id + result_hash on-chain (nullifier).
-    // Individual votes stay private in Ensue shared memory.
-    const resultKeys = workerDIDs.map(did => getWorkerKeys(did).RESULT);
-    const workerResults = await getEnsueClient().readMultiple(resultKeys);
-    const submissions = resultKeys
-      .map(key => {
-        const resultStr = workerResults[key];
-        if (!resultStr) return null;
-        try {
-          const result = JSON.parse(resultStr);
-          return {
-            worker_id: result.workerId as string,
-            result_hash: crypto.createHash('sha256').update(resultStr).digest('hex'),
-          };
-        } catch { return null; }
-      })
-      .filter((s): s is { worker_id: string; result_hash: string } => s !== null);
+    const submissions = await buildWorkerSubmissions(workerDIDs);
 
     // Production path: use ShadeClient v2 for contract call
     const { getAgent } = await import('../shade-client');
@@ -532,6 +512,13 @@ async function processCoordination(
       );
     }
 
+    // The contract refuses to settle below quorum; leave the proposal to time out
+    if (!tally.quorumReached) {
+      console.warn(`Participation ${tally.participation} is below quorum, proposal #${proposalId} will time out`);
+      await getEnsueClient().updateMemory(MEMORY_KEYS.COORDINATOR_STATUS, 'failed');
+      return;
+    }
+
     // Resume contract with results
     await resumeContractWithTally(proposalId, request, tally);
   } catch (error) {
@@ -643,7 +630,72 @@ async function waitForWorkers(workerDIDs: string[], timeout: number): Promise<bo
 }
 
 /**
- * Aggregate results from all workers — vote tally for DAO proposals,
+ * Register workers the contract doesn't know yet. Once any worker is registered,
+ * record_worker_submissions only accepts workers in the proposal's weight snapshot.
+ */
+async function registerMissingWorkers(workerDIDs: string[]): Promise<void> {
+  const registered = new Set((await localGetRegisteredWorkers()).map(w => w.worker_id));
+  for (const did of workerDIDs) {
+    if (!registered.has(did)) await localRegisterWorker(did);
+  }
+}
+
+interface ProposalWeights {
+  quorum: number;
+  worker_weights: { worker_id: string; weight: number }[];
+}
+
+/**
+ * Read the quorum and worker weight snapshot of an on-chain proposal
+ */
+async function getProposalWeights(proposalId: number): Promise<ProposalWeights | null> {
+  try {
+    if (LOCAL_MODE) {
+      return await localViewCall<ProposalWeights>('get_proposal', { proposal_id: proposalId });
+    }
+    const { getAgent } = await import('../shade-client');
+    return await getAgent().view<ProposalWeights>({
+      methodName: 'get_proposal',
+      args: { proposal_id: proposalId },
+    });
+  } catch (err) {
+    console.warn(`[aggregate] Could not read weights of proposal #${proposalId}:`, err);
+    return null;
+  }
+}
+
+/**
+ * How a parsed worker result counts on-chain: a vote task result with neither
+ * a vote nor a ranking abstains, every other result votes
+ */
+function submissionStatus(result: WorkerResult): SubmissionStatus {
+  if (result.taskType !== 'vote') return 'Voted';
+  if (result.output?.vote || result.output?.ranking?.length) return 'Voted';
+  return 'Abstained';
+}
+
+/**
+ * Build one on-chain submission per snapshot worker. Only worker_id, result_hash
+ * and status go on-chain (nullifier); individual votes stay private in Ensue.
+ * Missing or unparseable results are recorded as errored.
+ */
+async function buildWorkerSubmissions(workerDIDs: string[]): Promise<WorkerSubmissionInput[]> {
+  const resultKeys = workerDIDs.map(did => getWorkerKeys(did).RESULT);
+  const workerResults = await getEnsueClient().readMultiple(resultKeys);
+  return workerDIDs.map((did, i) => {
+    const resultStr = workerResults[resultKeys[i]] ?? '';
+    const result_hash = crypto.createHash('sha256').update(resultStr).digest('hex');
+    try {
+      const result: WorkerResult = JSON.parse(resultStr);
+      return { worker_id: result.workerId, result_hash, status: submissionStatus(result) };
+    } catch {
+      return { worker_id: did, result_hash, status: 'Errored' as const };
+    }
+  });
+}
+
+/**
+ * Aggregate results from all workers — weighted vote tally for DAO proposals,
  * sum for legacy numeric tasks.
  */
 async function aggregateResults(proposalId: number): Promise<TallyResult> {
@@ -669,6 +721,7 @@ async function aggregateResults(proposalId: number): Promise<TallyResult> {
     const parsed = JSON.parse(taskConfigStr ?? '{}');
     votingConfig = parsed?.parameters?.voting_config;
   } catch { /* ignore */ }
+  const proposalOptions = parseProposalOptions(taskConfigStr);
 
   const resultKeys = workerDIDs.map(did => getWorkerKeys(did).RESULT);
   const results = await getEnsueClient().readMultiple(resultKeys);
@@ -693,26 +746,43 @@ async function aggregateResults(proposalId: number): Promise<TallyResult> {
     }
   }
 
+  // Weigh each worker by the proposal's snapshot, as the contract does:
+  // every worker weighs 1 when no workers were registered at creation
+  const proposal = await getProposalWeights(proposalId);
+  const weights = new Map((proposal?.worker_weights ?? []).map(w => [w.worker_id, w.weight]));
+  const weightOf = (workerId: string) => weights.size === 0 ? 1 : weights.get(workerId) ?? 0;
+
   // Tally votes if any worker voted, otherwise sum values (backward compat)
   const hasVotes = workerResults.some(r => r.output?.vote);
   let approved = 0;
   let rejected = 0;
-
+  let abstained = 0;
+  let participation = 0;
+
+  // Participation as the contract records it: voters and abstainers count,
+  // errored workers (no parsed result) do not
+  for (const r of workerResults) {
+    const weight = weightOf(r.workerId);
+    participation += weight;
+    if (submissionStatus(r) === 'Abstained') abstained += weight;
+    else if (r.output?.vote === 'Approved') approved += weight;
+    else if (r.output?.vote === 'Rejected') rejected += weight;
+  }
+  const votingWeight = participation - abstained;
   if (hasVotes) {
-    for (const r of workerResults) {
-      if (r.output?.vote === 'Approved') approved++;
-      else if (r.output?.vote === 'Rejected') rejected++;
-    }
-    console.log(`\nVote tally: ${approved} Approved, ${rejected} Rejected`);
+    console.log(`\nWeighted vote tally: ${approved} Approved, ${rejected} Rejected, ${abstained} Abstained`);
   }
 
   const aggregatedValue = hasVotes
-    ? approved  // For vote tasks, aggregatedValue = number of approvals
+    ? approved  // For vote tasks, aggregatedValue = approving weight
     : workerResults.reduce((sum, r) => sum + (r.output?.value || 0), 0);
 
-  // Quorum-aware decision: use voting_config.quorum if set, otherwise strict majority
-  const minPositives = votingConfig?.quorum ?? (Math.floor(workerDIDs.length / 2) + 1);
+  // Quorum-aware decision, matching the contract: `quorum` approving weight if set,
+  // otherwise a strict majority of the voting weight
+  const quorum = proposal?.quorum || votingConfig?.quorum || 0;
+  const minPositives = quorum > 0 ? quorum : Math.floor(votingWeight / 2) + 1;
   const decision = hasVotes ? (approved >= minPositives ? 'Approved' : 'Rejected') : 'Approved';
+  const quorumReached = participation >= Math.max(quorum, 1);
 
   // Resolve display names for all participating workers
   let workerNames: Record<string, string> | undefined;
@@ -727,6 +797,9 @@ async function aggregateResults(proposalId: number): Promise<TallyResult> {
     aggregatedValue,
     approved,
     rejected,
+    abstained,
+    participation,
+    quorumReached,
     decision,
     workerCount: workerResults.length,
     workers: workerResults,
@@ -735,6 +808,12 @@ async function aggregateResults(proposalId: number): Promise<TallyResult> {
     workerNames,
   };
 
+  // Multi-option proposals settle on the option tally instead of approve/reject
+  if (proposalOptions) {
+    tally.optionTally = tallyOptions(proposalOptions, workerResults, weightOf);
+    console.log(`\nOption tally (${proposalOptions.resolution}):`, JSON.stringify(tally.optionTally));
+  }
+
   return tally;
 }
 
@@ -811,10 +890,12 @@ async function resumeContractWithTally(
     aggregatedValue: tally.aggregatedValue,
     approved: tally.approved,
     rejected: tally.rejected,
+    abstained: tally.abstained,
     decision: tally.decision,
     workerCount: tally.workerCount,
     timestamp: tally.timestamp,
     proposalId,
+    ...tally.optionTally,
   });
   const configHash = request.config_hash;
   const resultHash = crypto.createHash('sha256').update(onChainResult).digest('hex');
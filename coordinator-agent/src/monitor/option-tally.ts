@@ -0,0 +1,121 @@
+/**
+ * Option Tally — aggregates worker rankings for multi-option proposals.
+ *
+ * Produces the `{tally, ballots, winner}` payload that the coordinator
+ * contract's `resolve_options` validates. The winner rules mirror the
+ * contract: ties go to the option declared first, and instant-runoff
+ * eliminates the last-placed option (the latest declared on ties).
+ * Each worker counts with its weight in the proposal's snapshot, like the
+ * approve/reject tally.
+ */
+
+import type {
+  OptionTally,
+  RankedBallot,
+  ResolutionMode,
+  WorkerResult,
+} from '@near-shade-coordination/shared';
+
+export interface ProposalOptions {
+  options: string[];
+  resolution: ResolutionMode;
+}
+
+/**
+ * Read the declared options from a task config, or null for an
+ * approve/reject proposal.
+ */
+export function parseProposalOptions(taskConfig: string | null | undefined): ProposalOptions | null {
+  try {
+    const parameters = JSON.parse(taskConfig ?? '{}')?.parameters;
+    if (!Array.isArray(parameters?.options)) return null;
+    return {
+      options: parameters.options as string[],
+      resolution: (parameters.resolution as ResolutionMode | undefined) ?? 'Plurality',
+    };
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Tally worker rankings for the declared options, weighing each worker by `weightOf`.
+ * Unknown and repeated options in a ranking are dropped.
+ */
+export function tallyOptions(
+  declared: ProposalOptions,
+  workerResults: WorkerResult[],
+  weightOf: (workerId: string) => number,
+): OptionTally {
+  const ballots: RankedBallot[] = workerResults
+    .map(r => ({
+      ranking: cleanRanking(declared.options, r.output?.ranking),
+      weight: weightOf(r.workerId),
+    }))
+    .filter(ballot => ballot.ranking.length > 0 && ballot.weight > 0);
+
+  if (declared.resolution === 'InstantRunoff') {
+    const grouped = groupBallots(ballots);
+    return { tally: {}, ballots: grouped, winner: instantRunoff(declared.options, grouped) };
+  }
+
+  // A plurality voter backs its first choice, an approval voter every option it ranks
+  const tally: Record<string, number> = {};
+  for (const { ranking, weight } of ballots) {
+    const backed = declared.resolution === 'Approval' ? ranking : ranking.slice(0, 1);
+    for (const option of backed) tally[option] = (tally[option] ?? 0) + weight;
+  }
+  const counts = declared.options.map(o => tally[o] ?? 0);
+  const leader = leadingIndex(counts);
+  const winner = leader !== null && counts[leader] > 0 ? declared.options[leader] : null;
+  return { tally, ballots: [], winner };
+}
+
+function cleanRanking(options: string[], ranking: string[] | undefined): string[] {
+  const seen = new Set<string>();
+  for (const option of ranking ?? []) {
+    if (options.includes(option)) seen.add(option);
+  }
+  return [...seen];
+}
+
+/** Merge identical rankings into one ballot with their combined weight */
+function groupBallots(ballots: RankedBallot[]): RankedBallot[] {
+  const grouped = new Map<string, RankedBallot>();
+  for (const { ranking, weight } of ballots) {
+    const key = JSON.stringify(ranking);
+    const ballot = grouped.get(key);
+    if (ballot) ballot.weight += weight;
+    else grouped.set(key, { ranking, weight });
+  }
+  return [...grouped.values()];
+}
+
+/** Index of the highest count, the earliest one on ties */
+function leadingIndex(counts: number[]): number | null {
+  if (counts.length === 0) return null;
+  let leader = 0;
+  for (let i = 1; i < counts.length; i++) {
+    if (counts[i] > counts[leader]) leader = i;
+  }
+  return leader;
+}
+
+function instantRunoff(declared: string[], ballots: RankedBallot[]): string | null {
+  const remaining = [...declared];
+  for (;;) {
+    // Each ballot counts for its highest-ranked option still in the race
+    const counts = remaining.map(() => 0);
+    for (const ballot of ballots) {
+      const choice = ballot.ranking.find(o => remaining.includes(o));
+      if (choice !== undefined) counts[remaining.indexOf(choice)] += ballot.weight;
+    }
+    const active = counts.reduce((sum, count) => sum + count, 0);
+    if (active === 0) return null;
+    const leader = leadingIndex(counts) ?? 0;
+    if (counts[leader] * 2 > active || remaining.length === 1) return remaining[leader];
+    // Eliminate the last-placed option, the latest declared on ties
+    const lowest = Math.min(...counts);
+    remaining.splice(counts.lastIndexOf(lowest), 1);
+  }
+}
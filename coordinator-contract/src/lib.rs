@@ -7,12 +7,49 @@ use near_sdk::{
 };
 use serde_json::json;
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 
 // Gas constants (following verifiable-ai-dao/contract/src/dao.rs)
 const RETURN_RESULT_GAS: Gas = Gas::from_tgas(50);
 const FAIL_ON_TIMEOUT_GAS: Gas = Gas::from_tgas(10);
+const MIGRATE_GAS: Gas = Gas::from_tgas(30);
+const REQUEST_UPGRADE_GAS: Gas = Gas::from_tgas(200);
 const YIELD_REGISTER: u64 = 0;
 
+// Default worker bounds for contracts initialized without explicit config
+const DEFAULT_MIN_WORKERS: u8 = 1;
+const DEFAULT_MAX_WORKERS: u8 = u8::MAX;
+
+// TDX DCAP quote layout (v4): header, TD report body, signature data length
+const QUOTE_HEADER_LEN: usize = 48;
+const TD_REPORT_LEN: usize = 584;
+const TDX_QUOTE_VERSION: u16 = 4;
+const TDX_TEE_TYPE: u32 = 0x81;
+const TD_REPORT_MRTD_OFFSET: usize = 136;
+const TD_REPORT_RTMR_OFFSET: usize = 328;
+const TD_REPORT_DATA_OFFSET: usize = 520;
+const MEASUREMENT_REGISTER_LEN: usize = 48;
+
+// Default delay before a proposed manifesto change takes effect (24 hours)
+const DEFAULT_MANIFESTO_DELAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+// Shortest allowed manifesto delay (1 hour), so a change can't take effect unnoticed
+const MIN_MANIFESTO_DELAY_NS: u64 = 60 * 60 * 1_000_000_000;
+
+// Voting weight of a newly registered worker
+const DEFAULT_WORKER_WEIGHT: u32 = 1;
+
+// Default bond a challenger attaches to `challenge_result`
+const DEFAULT_CHALLENGE_BOND: NearToken = NearToken::from_near(1);
+
+// Legacy entries `migrate_step` copies per call, and the pause reason while it runs
+const MAX_MIGRATE_BATCH: u32 = 50;
+const MIGRATION_PAUSE_REASON: &str = "State migration in progress";
+
+// Limits for the options of a multi-option proposal
+const MAX_PROPOSAL_OPTIONS: usize = 32;
+const MAX_OPTION_LEN: usize = 256;
+
 #[derive(BorshStorageKey)]
 #[near]
 pub enum StorageKey {
@@ -20,10 +57,18 @@ pub enum StorageKey {
     // orphaned IterableMap/IterableSet entries at the old prefix, so we must
     // advance to fresh ordinals every time.
     _Dep0, _Dep1, _Dep2, _Dep3, _Dep4, _Dep5, _Dep6,
-    ApprovedCodehashes,     // ordinal 7
+    ApprovedCodehashSet,    // ordinal 7 (pre-metadata, read by migrate)
     CoordinatorByAccountId, // ordinal 8
-    Proposals,              // ordinal 9
-    RegisteredWorkers,      // ordinal 10
+    LegacyProposals,        // ordinal 9 (pre-weights, read by migrate)
+    LegacyRegisteredWorkers, // ordinal 10 (pre-attestation, read by migrate)
+    CouncilProposals,       // ordinal 11
+    ApprovedCodehashes,     // ordinal 12
+    RegisteredWorkers,      // ordinal 13
+    Proposals,              // ordinal 14
+    CoordinatorBonds,       // ordinal 15
+    Challenges,             // ordinal 16
+    RemovedCoordinators,    // ordinal 17
+    BondLocks,              // ordinal 18
 }
 
 /// Proposal lifecycle states
@@ -34,6 +79,8 @@ pub enum ProposalState {
     WorkersCompleted, // All worker submissions recorded on-chain
     Finalized,        // Aggregated result settled on-chain
     TimedOut,         // Yield timed out before resolution
+    Disputed,         // Finalized result has a pending challenge
+    Overturned,       // A challenge against the result was upheld
 }
 
 /// DAO manifesto that guides agent voting decisions
@@ -44,6 +91,60 @@ pub struct Manifesto {
     pub hash: String,
 }
 
+/// Manifesto change queued by `propose_manifesto`
+#[near(serializers = [json, borsh])]
+#[derive(Clone)]
+pub struct PendingManifesto {
+    pub manifesto: Manifesto,
+    pub proposed_at: u64,
+    /// Becomes the active manifesto from this timestamp (nanoseconds)
+    pub effective_at: u64,
+}
+
+/// Component a TEE codehash is approved for
+#[near(serializers = [json, borsh])]
+#[derive(Clone, PartialEq, Debug)]
+pub enum CodehashRole {
+    Coordinator,
+    Worker,
+}
+
+/// Approved TEE image and who approved it, for what, and until when
+#[near(serializers = [json, borsh])]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ApprovedCodehash {
+    pub codehash: String,
+    pub label: String,
+    pub role: CodehashRole,
+    pub approved_by: AccountId,
+    pub approved_at: u64,
+    /// Approval lapses at this timestamp (nanoseconds), if set
+    pub expires_at: Option<u64>,
+}
+
+impl ApprovedCodehash {
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        self.expires_at.is_none_or(|expires_at| timestamp < expires_at)
+    }
+}
+
+/// TEE attestation presented by an agent when registering
+#[near(serializers = [json])]
+#[derive(Clone)]
+pub struct Attestation {
+    /// Hex-encoded Intel TDX DCAP quote (v4)
+    pub quote_hex: String,
+    /// Measurement the agent claims; must match the quote and be approved
+    pub codehash: String,
+}
+
+/// Fields of a TDX quote the contract checks
+pub struct ParsedQuote {
+    /// SHA-256 (hex) over MRTD and RTMR0-3 of the TD report
+    pub measurement: String,
+    pub report_data: [u8; 64],
+}
+
 /// Worker/coordinator registration information (TEE attestation)
 #[near(serializers = [json, borsh])]
 #[derive(Clone)]
@@ -61,6 +162,35 @@ pub struct RegisteredWorker {
     pub registered_at: u64,
     pub registered_by: AccountId,
     pub active: bool,
+    /// Set when the worker self-registered with a TEE attestation
+    pub attestation: Option<Worker>,
+    /// Voting weight, managed by the owner via `set_worker_weight`
+    pub weight: u32,
+}
+
+/// `RegisteredWorker` before attestation data was added.
+/// Read by `migrate` when upgrading an older deployment.
+#[near(serializers = [borsh])]
+pub struct RegisteredWorkerV1 {
+    pub worker_id: String,
+    pub account_id: Option<AccountId>,
+    pub registered_at: u64,
+    pub registered_by: AccountId,
+    pub active: bool,
+}
+
+impl RegisteredWorkerV1 {
+    fn into_current(self) -> RegisteredWorker {
+        RegisteredWorker {
+            worker_id: self.worker_id,
+            account_id: self.account_id,
+            registered_at: self.registered_at,
+            registered_by: self.registered_by,
+            active: self.active,
+            attestation: None,
+            weight: DEFAULT_WORKER_WEIGHT,
+        }
+    }
 }
 
 /// Input format for recording worker submissions (nullifier only — no vote data on-chain)
@@ -69,6 +199,21 @@ pub struct RegisteredWorker {
 pub struct WorkerSubmissionInput {
     pub worker_id: String,
     pub result_hash: String,
+    #[serde(default)]
+    pub status: SubmissionStatus,
+}
+
+/// Outcome of a worker's participation in a proposal
+#[near(serializers = [json, borsh])]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum SubmissionStatus {
+    /// Cast a vote that counts toward the decision
+    #[default]
+    Voted,
+    /// Participated without taking a side; counts toward participation only
+    Abstained,
+    /// Failed to produce a vote; recorded so degraded votes stay visible
+    Errored,
 }
 
 /// On-chain record of a worker's submission (nullifier + proof of participation)
@@ -79,6 +224,122 @@ pub struct WorkerSubmission {
     pub worker_id: String,
     pub result_hash: String,
     pub timestamp: u64,
+    pub status: SubmissionStatus,
+}
+
+/// Result hash a worker posted from its bound account
+#[near(serializers = [json, borsh])]
+#[derive(Clone)]
+pub struct BoundResult {
+    pub worker_id: String,
+    pub result_hash: String,
+}
+
+/// `WorkerSubmission` before submission statuses were added.
+/// Read by `migrate` when upgrading an older deployment.
+#[near(serializers = [borsh])]
+pub struct WorkerSubmissionV1 {
+    pub worker_id: String,
+    pub result_hash: String,
+    pub timestamp: u64,
+}
+
+/// How the winning option of a multi-option proposal is chosen
+#[near(serializers = [json, borsh])]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum ResolutionMode {
+    /// Each voter picks one option; the most weight wins
+    #[default]
+    Plurality,
+    /// Each voter approves any number of options; the most approved wins
+    Approval,
+    /// Voters rank options; the last-placed option is eliminated until one
+    /// holds a majority of the remaining ballots
+    InstantRunoff,
+}
+
+/// Options declared by a multi-option proposal in its task config
+#[near(serializers = [json, borsh])]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProposalOptions {
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub resolution: ResolutionMode,
+}
+
+/// Per-proposal overrides in a task config's `parameters.voting_config`
+#[near(serializers = [json])]
+#[derive(Clone, Default)]
+pub struct VotingConfig {
+    pub min_workers: Option<u8>,
+    /// Approvals needed; `quorum` passed to `start_coordination` must match
+    pub quorum: Option<u8>,
+}
+
+/// Task parameters. Fields used by specific task types (e.g. `proposalId`)
+/// are passed through to the agents unchecked.
+#[near(serializers = [json])]
+#[derive(Clone, Default)]
+pub struct TaskParameters {
+    /// Proposal text, required for `vote` tasks
+    pub proposal: Option<String>,
+    pub voting_config: Option<VotingConfig>,
+    /// Options of a multi-option proposal
+    pub options: Option<Vec<String>>,
+    #[serde(default)]
+    pub resolution: ResolutionMode,
+}
+
+/// Typed `task_config` accepted by `start_coordination`
+#[near(serializers = [json])]
+#[derive(Clone)]
+pub struct TaskConfig {
+    #[serde(rename = "type")]
+    pub task_type: String,
+    #[serde(default)]
+    pub parameters: TaskParameters,
+    /// Worker time budget in milliseconds
+    pub timeout: Option<u64>,
+    /// Free-form JSON object for the requester's own use
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl TaskConfig {
+    pub fn proposal_options(&self) -> Option<ProposalOptions> {
+        self.parameters.options.as_ref().map(|options| ProposalOptions {
+            options: options.clone(),
+            resolution: self.parameters.resolution,
+        })
+    }
+}
+
+/// Ranked ballots that share the same ranking, with their combined weight
+#[near(serializers = [json])]
+#[derive(Clone)]
+pub struct RankedBallot {
+    pub ranking: Vec<String>,
+    pub weight: u64,
+}
+
+/// Tally reported in the aggregated result of a multi-option proposal:
+/// weight per option for plurality and approval, ranked ballots for
+/// instant-runoff. `winner`, if reported, must match the contract's result.
+#[near(serializers = [json])]
+#[derive(Clone, Default)]
+pub struct OptionTally {
+    #[serde(default)]
+    pub tally: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub ballots: Vec<RankedBallot>,
+    pub winner: Option<String>,
+}
+
+/// Voting weight of a worker, snapshotted when a proposal is created
+#[near(serializers = [json, borsh])]
+#[derive(Clone)]
+pub struct WorkerWeight {
+    pub worker_id: String,
+    pub weight: u32,
 }
 
 /// Unified proposal struct with full lifecycle tracking
@@ -95,6 +356,221 @@ pub struct Proposal {
     pub quorum: u8,
     pub worker_submissions: Vec<WorkerSubmission>,
     pub finalized_result: Option<String>,
+    /// Active workers and their weights at creation. Empty when no workers
+    /// were registered, in which case every submission weighs 1.
+    pub worker_weights: Vec<WorkerWeight>,
+    /// Sum of the snapshotted weights (`expected_worker_count` if none)
+    pub total_weight: u64,
+    /// Sum of the weights of the workers that voted or abstained
+    pub participating_weight: u64,
+    /// Part of `participating_weight` that abstained
+    pub abstained_weight: u64,
+    /// Declared options; `None` for an approve/reject proposal
+    pub options: Option<ProposalOptions>,
+    /// Option chosen by the tally once finalized
+    pub winning_option: Option<String>,
+    /// Coordinator that settled the result; its bond backs the result
+    pub resolved_by: Option<AccountId>,
+    /// Last moment the finalized result can be challenged, if challenges are on
+    pub challenge_deadline: Option<u64>,
+    /// Hash of the manifesto active when the proposal was created
+    pub manifesto_hash: Option<String>,
+    /// Result hashes posted by bound worker accounts via `submit_worker_result`
+    pub bound_results: Vec<BoundResult>,
+}
+
+impl Proposal {
+    /// Weight that counts toward the decision: participants that did not abstain
+    pub fn voting_weight(&self) -> u64 {
+        self.participating_weight - self.abstained_weight
+    }
+
+    /// Whether enough weight voted or abstained to settle: at least `quorum`,
+    /// or any participation when no quorum is set
+    pub fn has_participation_quorum(&self) -> bool {
+        self.participating_weight >= (self.quorum as u64).max(1)
+    }
+
+    /// Weight of a worker in this proposal, or `None` if it is not eligible
+    pub fn weight_of(&self, worker_id: &str) -> Option<u32> {
+        if self.worker_weights.is_empty() {
+            return Some(DEFAULT_WORKER_WEIGHT);
+        }
+        self.worker_weights
+            .iter()
+            .find(|w| w.worker_id == worker_id)
+            .map(|w| w.weight)
+    }
+}
+
+/// `Proposal` before worker weights were added.
+/// Read by `migrate` when upgrading an older deployment.
+#[near(serializers = [borsh])]
+pub struct ProposalV1 {
+    pub yield_id: CryptoHash,
+    pub task_config: String,
+    pub config_hash: String,
+    pub timestamp: u64,
+    pub requester: AccountId,
+    pub state: ProposalState,
+    pub expected_worker_count: u8,
+    pub quorum: u8,
+    pub worker_submissions: Vec<WorkerSubmissionV1>,
+    pub finalized_result: Option<String>,
+}
+
+impl ProposalV1 {
+    /// Proposals predate weights: every recorded submission weighed 1
+    fn into_current(self) -> Proposal {
+        Proposal {
+            yield_id: self.yield_id,
+            task_config: self.task_config,
+            config_hash: self.config_hash,
+            timestamp: self.timestamp,
+            requester: self.requester,
+            state: self.state,
+            expected_worker_count: self.expected_worker_count,
+            quorum: self.quorum,
+            total_weight: self.expected_worker_count as u64,
+            participating_weight: self.worker_submissions.len() as u64,
+            worker_submissions: self
+                .worker_submissions
+                .into_iter()
+                .map(|s| WorkerSubmission {
+                    worker_id: s.worker_id,
+                    result_hash: s.result_hash,
+                    timestamp: s.timestamp,
+                    status: SubmissionStatus::Voted,
+                })
+                .collect(),
+            finalized_result: self.finalized_result,
+            worker_weights: Vec::new(),
+            abstained_weight: 0,
+            options: None,
+            winning_option: None,
+            resolved_by: None,
+            challenge_deadline: None,
+            manifesto_hash: None,
+            bound_results: Vec::new(),
+        }
+    }
+}
+
+/// Settings for challenging finalized results
+#[near(serializers = [json, borsh])]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChallengeConfig {
+    /// How long a finalized result can be challenged; 0 disables challenges
+    pub period_ns: u64,
+    /// Deposit a challenger attaches, refunded if the challenge is upheld
+    pub challenge_bond: NearToken,
+    /// Bond a coordinator must hold to settle results
+    pub min_coordinator_bond: NearToken,
+}
+
+impl Default for ChallengeConfig {
+    fn default() -> Self {
+        Self {
+            period_ns: 0,
+            challenge_bond: DEFAULT_CHALLENGE_BOND,
+            min_coordinator_bond: NearToken::from_near(0),
+        }
+    }
+}
+
+#[near(serializers = [json, borsh])]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChallengeStatus {
+    Pending,
+    Upheld,
+    Dismissed,
+}
+
+/// Bonded claim that a finalized result misreports a worker's submission
+#[near(serializers = [json, borsh])]
+#[derive(Clone)]
+pub struct Challenge {
+    pub id: u64,
+    pub proposal_id: u64,
+    pub worker_id: String,
+    pub reason: String,
+    pub challenger: AccountId,
+    pub bond: NearToken,
+    pub created_at: u64,
+    pub status: ChallengeStatus,
+}
+
+/// What keeps a coordinator from withdrawing its bond
+#[near(serializers = [json, borsh])]
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct BondLock {
+    /// Latest challenge deadline of a result the coordinator settled
+    pub locked_until: u64,
+    /// Results the coordinator settled that are currently disputed
+    pub open_disputes: u32,
+}
+
+/// Emergency pause set by the owner
+#[near(serializers = [json, borsh])]
+#[derive(Clone)]
+pub struct PauseInfo {
+    pub reason: String,
+    pub paused_at: u64,
+}
+
+/// M-of-N owner council. While configured, sensitive owner actions are
+/// council proposals that execute once `threshold` members confirm.
+#[near(serializers = [json, borsh])]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Council {
+    pub members: Vec<AccountId>,
+    pub threshold: u8,
+}
+
+/// Owner action that requires council confirmation
+#[near(serializers = [json, borsh])]
+#[derive(Clone, PartialEq, Debug)]
+pub enum CouncilAction {
+    ProposeManifesto { manifesto_text: String },
+    CancelManifestoChange,
+    SetManifestoDelay { delay_ns: u64 },
+    ApproveCodehash {
+        codehash: String,
+        label: Option<String>,
+        role: Option<CodehashRole>,
+        expires_at: Option<u64>,
+    },
+    RemoveCodehash { codehash: String },
+    UnregisterCoordinator { account_id: AccountId },
+    ReinstateCoordinator { account_id: AccountId },
+    ClearProposal { proposal_id: u64 },
+    RemoveWorker { worker_id: String },
+    SetWorkerActive { worker_id: String, active: bool },
+    SetWorkerWeight { worker_id: String, weight: u32 },
+    SetRequireWorkerBinding { required: bool },
+    SetLegacyTaskConfig { enabled: bool },
+    Pause { reason: String },
+    Unpause,
+    ProposeOwner { new_owner: AccountId },
+    CancelOwnershipTransfer,
+    /// Ask the deploying factory to upgrade this contract to `code_hash`
+    /// (or its default version). Confirm with at least 250 TGas attached.
+    UpgradeCode { code_hash: Option<String> },
+    /// Replace the council, or remove it with `None`
+    SetCouncil { council: Option<Council> },
+    SetChallengeConfig { config: ChallengeConfig },
+    ResolveChallenge { challenge_id: u64, upheld: bool },
+}
+
+/// Pending council action and the members that confirmed it
+#[near(serializers = [json, borsh])]
+#[derive(Clone)]
+pub struct CouncilProposal {
+    pub id: u64,
+    pub action: CouncilAction,
+    pub proposed_by: AccountId,
+    pub confirmations: Vec<AccountId>,
+    pub created_at: u64,
 }
 
 /// Main contract state
@@ -102,12 +578,70 @@ pub struct Proposal {
 #[derive(PanicOnDefault)]
 pub struct CoordinatorContract {
     pub owner: AccountId,
-    pub approved_codehashes: IterableSet<String>,
+    pub approved_codehashes: IterableMap<String, ApprovedCodehash>,
     pub coordinator_by_account_id: IterableMap<AccountId, Worker>,
     pub current_proposal_id: u64,
     pub proposals: IterableMap<u64, Proposal>,
     pub manifesto: Option<Manifesto>,
     pub registered_workers: IterableMap<String, RegisteredWorker>,
+    pub min_workers: u8,
+    pub max_workers: u8,
+    /// Platform registry this coordinator is listed in, if any
+    pub registry_contract_id: Option<AccountId>,
+    /// Factory that deployed this contract; allowed to push code upgrades
+    pub factory_account_id: Option<AccountId>,
+    /// Set while coordination is frozen by the owner
+    pub paused: Option<PauseInfo>,
+    /// Proposed owner awaiting `accept_ownership`
+    pub pending_owner: Option<AccountId>,
+    pub council: Option<Council>,
+    pub council_proposals: IterableMap<u64, CouncilProposal>,
+    pub next_council_proposal_id: u64,
+    /// Queued manifesto change; activated lazily once `effective_at` passes
+    pub pending_manifesto: Option<PendingManifesto>,
+    pub manifesto_delay_ns: u64,
+    /// Require each recorded submission to carry the worker's bound account
+    pub require_worker_binding: bool,
+    /// Accept any `task_config` string instead of a typed `TaskConfig`
+    pub legacy_task_config: bool,
+    pub challenge_config: ChallengeConfig,
+    pub coordinator_bonds: IterableMap<AccountId, NearToken>,
+    pub bond_locks: IterableMap<AccountId, BondLock>,
+    pub challenges: IterableMap<u64, Challenge>,
+    pub next_challenge_id: u64,
+    /// Old-layout collections `migrate_step` has not copied yet
+    pub legacy_collections: Option<LegacyCollections>,
+    /// Unregistered coordinators; they cannot register again until reinstated
+    pub removed_coordinators: IterableSet<AccountId>,
+}
+
+/// Collections of an old state layout, copied in batches by `migrate_step`
+#[near(serializers = [borsh])]
+pub struct LegacyCollections {
+    pub approved_codehashes: IterableSet<String>,
+    pub registered_workers: IterableMap<String, RegisteredWorkerV1>,
+    pub proposals: IterableMap<u64, ProposalV1>,
+}
+
+impl LegacyCollections {
+    fn is_empty(&self) -> bool {
+        self.approved_codehashes.is_empty()
+            && self.registered_workers.is_empty()
+            && self.proposals.is_empty()
+    }
+}
+
+/// State layout of the original release, before worker bounds, registry and
+/// factory fields were added. Read by `migrate` when upgrading a deployment.
+#[near(serializers = [borsh])]
+pub struct CoordinatorContractV1 {
+    pub owner: AccountId,
+    pub approved_codehashes: IterableSet<String>,
+    pub coordinator_by_account_id: IterableMap<AccountId, Worker>,
+    pub current_proposal_id: u64,
+    pub proposals: IterableMap<u64, ProposalV1>,
+    pub manifesto: Option<Manifesto>,
+    pub registered_workers: IterableMap<String, RegisteredWorkerV1>,
 }
 
 #[near]
@@ -116,25 +650,258 @@ impl CoordinatorContract {
     #[init]
     #[private]
     pub fn new(owner: AccountId) -> Self {
-        Self {
+        Self::internal_new(
+            owner,
+            None,
+            DEFAULT_MIN_WORKERS,
+            DEFAULT_MAX_WORKERS,
+            Vec::new(),
+            None,
+        )
+    }
+
+    /// Initialize the contract with its full starting configuration.
+    /// Used by the coordinator factory to set everything up in the deploy batch,
+    /// so besides the contract itself the parent account may call it: the
+    /// factory creates coordinators as its sub-accounts. When the parent calls,
+    /// it is recorded as the factory allowed to push upgrades via `update_code`.
+    #[init]
+    pub fn new_with_config(
+        owner: AccountId,
+        manifesto_text: Option<String>,
+        min_workers: u8,
+        max_workers: u8,
+        approved_codehashes: Vec<String>,
+        registry_contract_id: Option<AccountId>,
+    ) -> Self {
+        let predecessor = env::predecessor_account_id();
+        require!(
+            predecessor == env::current_account_id()
+                || env::current_account_id().is_sub_account_of(&predecessor),
+            "Only this account or its parent factory can initialize"
+        );
+        let mut contract = Self::internal_new(
+            owner,
+            manifesto_text,
+            min_workers,
+            max_workers,
+            approved_codehashes,
+            registry_contract_id,
+        );
+        if predecessor != env::current_account_id() {
+            contract.factory_account_id = Some(predecessor);
+        }
+        contract
+    }
+
+    fn internal_new(
+        owner: AccountId,
+        manifesto_text: Option<String>,
+        min_workers: u8,
+        max_workers: u8,
+        approved_codehashes: Vec<String>,
+        registry_contract_id: Option<AccountId>,
+    ) -> Self {
+        require!(min_workers >= 1, "min_workers must be >= 1");
+        require!(
+            max_workers >= min_workers,
+            "max_workers must be >= min_workers"
+        );
+        let mut contract = Self {
             owner,
-            approved_codehashes: IterableSet::new(StorageKey::ApprovedCodehashes),
+            approved_codehashes: IterableMap::new(StorageKey::ApprovedCodehashes),
             coordinator_by_account_id: IterableMap::new(StorageKey::CoordinatorByAccountId),
             current_proposal_id: 0,
             proposals: IterableMap::new(StorageKey::Proposals),
             manifesto: None,
             registered_workers: IterableMap::new(StorageKey::RegisteredWorkers),
+            min_workers,
+            max_workers,
+            registry_contract_id,
+            factory_account_id: None,
+            paused: None,
+            pending_owner: None,
+            council: None,
+            council_proposals: IterableMap::new(StorageKey::CouncilProposals),
+            next_council_proposal_id: 0,
+            pending_manifesto: None,
+            manifesto_delay_ns: DEFAULT_MANIFESTO_DELAY_NS,
+            require_worker_binding: false,
+            legacy_task_config: false,
+            challenge_config: ChallengeConfig::default(),
+            coordinator_bonds: IterableMap::new(StorageKey::CoordinatorBonds),
+            bond_locks: IterableMap::new(StorageKey::BondLocks),
+            challenges: IterableMap::new(StorageKey::Challenges),
+            next_challenge_id: 0,
+            legacy_collections: None,
+            removed_coordinators: IterableSet::new(StorageKey::RemovedCoordinators),
+        };
+        if let Some(text) = manifesto_text {
+            contract.manifesto = Some(build_manifesto(text));
+        }
+        let now = env::block_timestamp();
+        for codehash in approved_codehashes {
+            let approved = ApprovedCodehash {
+                codehash: codehash.clone(),
+                label: String::new(),
+                role: CodehashRole::Coordinator,
+                approved_by: contract.owner.clone(),
+                approved_at: now,
+                expires_at: None,
+            };
+            contract.approved_codehashes.insert(codehash, approved);
         }
+        contract
     }
 
-    /// Migrate from previous version (adds registered_workers field).
-    /// Reads old state (without registered_workers), preserves all data,
-    /// and adds the new field.
+    /// Migrate state after deploying new code, preserving all data
+    /// (including the manifesto). Accepts the current layout or the
+    /// `CoordinatorContractV1` layout, which gets default worker bounds and no
+    /// registry/factory.
+    ///
+    /// Only the root state is converted here, so the call fits in `MIGRATE_GAS`
+    /// however many records exist. Old codehashes, workers and proposals are
+    /// copied by `migrate_step`; coordination stays paused until it finishes.
+    #[init(ignore_state)]
     #[private]
-    pub fn migrate(&mut self) {
-        // This is a no-op if already migrated — the method just needs to
-        // exist so we can call it after deploying new code.
-        // The actual migration happens through #[init(ignore_state)] below.
+    pub fn migrate() -> Self {
+        let raw = env::storage_read(b"STATE").expect("No contract state to migrate");
+        if let Ok(current) = near_sdk::borsh::from_slice::<Self>(&raw) {
+            return current;
+        }
+        let old = near_sdk::borsh::from_slice::<CoordinatorContractV1>(&raw)
+            .unwrap_or_else(|_| env::panic_str("Unrecognized state layout, use force_migrate"));
+        env::log_str("Migrated state from V1 layout");
+        let legacy = LegacyCollections {
+            approved_codehashes: old.approved_codehashes,
+            registered_workers: old.registered_workers,
+            proposals: old.proposals,
+        };
+        let (paused, legacy_collections) = if legacy.is_empty() {
+            (None, None)
+        } else {
+            let pause = PauseInfo {
+                reason: MIGRATION_PAUSE_REASON.to_string(),
+                paused_at: env::block_timestamp(),
+            };
+            (Some(pause), Some(legacy))
+        };
+        Self {
+            owner: old.owner,
+            approved_codehashes: IterableMap::new(StorageKey::ApprovedCodehashes),
+            coordinator_by_account_id: old.coordinator_by_account_id,
+            current_proposal_id: old.current_proposal_id,
+            proposals: IterableMap::new(StorageKey::Proposals),
+            manifesto: old.manifesto,
+            registered_workers: IterableMap::new(StorageKey::RegisteredWorkers),
+            min_workers: DEFAULT_MIN_WORKERS,
+            max_workers: DEFAULT_MAX_WORKERS,
+            registry_contract_id: None,
+            factory_account_id: None,
+            paused,
+            pending_owner: None,
+            council: None,
+            council_proposals: IterableMap::new(StorageKey::CouncilProposals),
+            next_council_proposal_id: 0,
+            pending_manifesto: None,
+            manifesto_delay_ns: DEFAULT_MANIFESTO_DELAY_NS,
+            require_worker_binding: false,
+            // Keep accepting the configs existing requesters send
+            legacy_task_config: true,
+            challenge_config: ChallengeConfig::default(),
+            coordinator_bonds: IterableMap::new(StorageKey::CoordinatorBonds),
+            bond_locks: IterableMap::new(StorageKey::BondLocks),
+            challenges: IterableMap::new(StorageKey::Challenges),
+            next_challenge_id: 0,
+            legacy_collections,
+            removed_coordinators: IterableSet::new(StorageKey::RemovedCoordinators),
+        }
+    }
+
+    /// Copy up to `limit` (capped at 50) old-layout records left by `migrate`
+    /// into the current collections. Permissionless. Lifts the migration pause
+    /// once everything is copied and returns whether migration is complete.
+    pub fn migrate_step(&mut self, limit: Option<u32>) -> bool {
+        let mut legacy = self
+            .legacy_collections
+            .take()
+            .unwrap_or_else(|| env::panic_str("No migration in progress"));
+        let mut budget = limit.unwrap_or(MAX_MIGRATE_BATCH).min(MAX_MIGRATE_BATCH) as usize;
+        let now = env::block_timestamp();
+
+        // Approved codehashes were a bare set; they carry over as coordinator
+        // images approved by the owner at migration time, without expiry.
+        let codehashes: Vec<String> =
+            legacy.approved_codehashes.iter().take(budget).cloned().collect();
+        budget -= codehashes.len();
+        for codehash in codehashes {
+            legacy.approved_codehashes.remove(&codehash);
+            let approved = ApprovedCodehash {
+                codehash: codehash.clone(),
+                label: String::new(),
+                role: CodehashRole::Coordinator,
+                approved_by: self.owner.clone(),
+                approved_at: now,
+                expires_at: None,
+            };
+            self.approved_codehashes.insert(codehash, approved);
+        }
+
+        let worker_ids: Vec<String> =
+            legacy.registered_workers.keys().take(budget).cloned().collect();
+        budget -= worker_ids.len();
+        for worker_id in worker_ids {
+            let worker = legacy.registered_workers.remove(&worker_id).unwrap();
+            // Workers registered since the upgrade take precedence
+            if !self.registered_workers.contains_key(&worker_id) {
+                self.registered_workers.insert(worker_id, worker.into_current());
+            }
+        }
+
+        let proposal_ids: Vec<u64> = legacy.proposals.keys().take(budget).copied().collect();
+        for proposal_id in proposal_ids {
+            let proposal = legacy.proposals.remove(&proposal_id).unwrap();
+            self.proposals.insert(proposal_id, proposal.into_current());
+        }
+
+        if !legacy.is_empty() {
+            self.legacy_collections = Some(legacy);
+            return false;
+        }
+        if self
+            .paused
+            .as_ref()
+            .is_some_and(|p| p.reason == MIGRATION_PAUSE_REASON)
+        {
+            self.paused = None;
+        }
+        env::log_str("State migration complete");
+        true
+    }
+
+    /// Whether `migrate_step` still has old-layout records to copy
+    pub fn is_migration_pending(&self) -> bool {
+        self.legacy_collections.is_some()
+    }
+
+    /// Deploy new code pushed by the factory, then call `migrate`.
+    /// The raw WASM is the call input. Only the factory that deployed this
+    /// contract can call it; the factory checks the request came from the owner.
+    pub fn update_code(&self) -> Promise {
+        require!(
+            self.factory_account_id.as_ref() == Some(&env::predecessor_account_id()),
+            "Only the deploying factory can update code"
+        );
+        let code = env::input().unwrap_or_else(|| env::panic_str("No code provided"));
+        env::log_str(&format!("Updating code ({} bytes) from factory", code.len()));
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                "migrate".to_string(),
+                Vec::new(),
+                NearToken::from_near(0),
+                MIGRATE_GAS,
+            )
     }
 
     /// Re-initialize from scratch, preserving IterableMap data at same storage prefixes.
@@ -144,35 +911,88 @@ impl CoordinatorContract {
     pub fn force_migrate(owner: AccountId, current_proposal_id: u64) -> Self {
         Self {
             owner,
-            approved_codehashes: IterableSet::new(StorageKey::ApprovedCodehashes),
+            approved_codehashes: IterableMap::new(StorageKey::ApprovedCodehashes),
             coordinator_by_account_id: IterableMap::new(StorageKey::CoordinatorByAccountId),
             current_proposal_id,
             proposals: IterableMap::new(StorageKey::Proposals),
             manifesto: None, // Will need to be re-set via set_manifesto
             registered_workers: IterableMap::new(StorageKey::RegisteredWorkers),
+            min_workers: DEFAULT_MIN_WORKERS,
+            max_workers: DEFAULT_MAX_WORKERS,
+            registry_contract_id: None,
+            factory_account_id: None,
+            paused: None,
+            pending_owner: None,
+            council: None,
+            council_proposals: IterableMap::new(StorageKey::CouncilProposals),
+            next_council_proposal_id: 0,
+            pending_manifesto: None,
+            manifesto_delay_ns: DEFAULT_MANIFESTO_DELAY_NS,
+            require_worker_binding: false,
+            // Keep accepting the configs existing requesters send
+            legacy_task_config: true,
+            challenge_config: ChallengeConfig::default(),
+            coordinator_bonds: IterableMap::new(StorageKey::CoordinatorBonds),
+            bond_locks: IterableMap::new(StorageKey::BondLocks),
+            challenges: IterableMap::new(StorageKey::Challenges),
+            next_challenge_id: 0,
+            legacy_collections: None,
+            removed_coordinators: IterableSet::new(StorageKey::RemovedCoordinators),
         }
     }
 
     // ========== MANIFESTO ==========
 
-    /// Set the DAO manifesto that guides agent voting decisions
+    /// Set the initial DAO manifesto that guides agent voting decisions.
+    /// Later changes go through `propose_manifesto` and its delay.
     pub fn set_manifesto(&mut self, manifesto_text: String) {
-        self.require_owner();
+        self.require_owner_action();
         require!(
-            manifesto_text.len() <= 10000,
-            "Manifesto text needs to be under 10,000 characters"
+            self.manifesto.is_none() && self.pending_manifesto.is_none(),
+            "Manifesto already set. Use propose_manifesto to change it."
         );
-        let manifesto_hash = hash(&manifesto_text);
-        self.manifesto = Some(Manifesto {
-            text: manifesto_text,
-            hash: manifesto_hash.clone(),
-        });
-        env::log_str(&format!("Manifesto set (hash: {})", manifesto_hash));
+        let manifesto = build_manifesto(manifesto_text);
+        env::log_str(&format!("Manifesto set (hash: {})", manifesto.hash));
+        self.manifesto = Some(manifesto);
+    }
+
+    /// Queue a manifesto change that takes effect after `manifesto_delay_ns`
+    pub fn propose_manifesto(&mut self, manifesto_text: String) {
+        self.require_owner_action();
+        self.internal_propose_manifesto(manifesto_text);
     }
 
-    /// Get the current manifesto
+    /// Drop the queued manifesto change before it takes effect
+    pub fn cancel_manifesto_change(&mut self) {
+        self.require_owner_action();
+        self.internal_cancel_manifesto_change();
+    }
+
+    /// Set the delay for future manifesto changes (nanoseconds)
+    pub fn set_manifesto_delay(&mut self, delay_ns: u64) {
+        self.require_owner_action();
+        self.internal_set_manifesto_delay(delay_ns);
+    }
+
+    /// Get the active manifesto, including a queued change whose delay has passed
     pub fn get_manifesto(&self) -> Option<Manifesto> {
-        self.manifesto.clone()
+        match &self.pending_manifesto {
+            Some(pending) if pending.effective_at <= env::block_timestamp() => {
+                Some(pending.manifesto.clone())
+            }
+            _ => self.manifesto.clone(),
+        }
+    }
+
+    /// Get the queued manifesto change that is not active yet
+    pub fn get_pending_manifesto(&self) -> Option<PendingManifesto> {
+        self.pending_manifesto
+            .clone()
+            .filter(|p| p.effective_at > env::block_timestamp())
+    }
+
+    pub fn get_manifesto_delay(&self) -> u64 {
+        self.manifesto_delay_ns
     }
 
     // ========== COORDINATION ==========
@@ -185,20 +1005,42 @@ impl CoordinatorContract {
         expected_worker_count: u8,
         quorum: u8,
     ) -> u64 {
-        require!(
-            self.manifesto.is_some(),
-            "Manifesto not set. Owner must set_manifesto first."
-        );
+        self.require_not_paused();
+        self.activate_pending_manifesto();
+        let manifesto_hash = self
+            .manifesto
+            .as_ref()
+            .map(|m| m.hash.clone())
+            .unwrap_or_else(|| env::panic_str("Manifesto not set. Owner must set_manifesto first."));
         require!(
             task_config.len() <= 10000,
             "Task config needs to be under 10,000 characters"
         );
+        require!(
+            expected_worker_count >= self.min_workers && expected_worker_count <= self.max_workers,
+            format!(
+                "expected_worker_count must be between {} and {}",
+                self.min_workers, self.max_workers
+            )
+        );
+        // Snapshot weights so later weight changes cannot sway this proposal
+        let worker_weights = self.snapshot_worker_weights();
+        let total_weight = if worker_weights.is_empty() {
+            expected_worker_count as u64
+        } else {
+            worker_weights.iter().map(|w| w.weight as u64).sum()
+        };
+        require!(
+            quorum as u64 <= total_weight,
+            format!("quorum must be <= total eligible weight ({})", total_weight)
+        );
 
         self.current_proposal_id += 1;
         let proposal_id = self.current_proposal_id;
         let requester = env::predecessor_account_id();
         let timestamp = env::block_timestamp();
         let config_hash = hash(&task_config);
+        let options = self.task_config_options(&task_config, expected_worker_count, quorum);
 
         // Create yielded promise with callback
         let _yielded_promise = env::promise_yield_create(
@@ -232,17 +1074,73 @@ impl CoordinatorContract {
             quorum,
             worker_submissions: Vec::new(),
             finalized_result: None,
+            worker_weights,
+            total_weight,
+            participating_weight: 0,
+            abstained_weight: 0,
+            options,
+            winning_option: None,
+            resolved_by: None,
+            challenge_deadline: None,
+            manifesto_hash: Some(manifesto_hash.clone()),
+            bound_results: Vec::new(),
         };
         self.proposals.insert(proposal_id, proposal);
 
         env::log_str(&format!(
-            "Created proposal #{} with config_hash: {}",
-            proposal_id, config_hash
+            "Created proposal #{} with config_hash: {} (manifesto: {})",
+            proposal_id, config_hash, manifesto_hash
         ));
 
         proposal_id
     }
 
+    /// Post a worker's result hash from its bound account. With
+    /// `require_worker_binding` on, the coordinator can only record results
+    /// the workers posted here themselves.
+    pub fn submit_worker_result(
+        &mut self,
+        proposal_id: u64,
+        worker_id: String,
+        result_hash: String,
+    ) {
+        self.require_not_paused();
+        let caller = env::predecessor_account_id();
+        let worker = self
+            .registered_workers
+            .get(&worker_id)
+            .unwrap_or_else(|| env::panic_str(&format!("Worker {} not registered", worker_id)));
+        require!(worker.active, format!("Worker {} is deactivated", worker_id));
+        require!(
+            worker.account_id.as_ref() == Some(&caller),
+            format!("Only the bound account of worker {} can post its result", worker_id)
+        );
+        let proposal = self
+            .proposals
+            .get_mut(&proposal_id)
+            .expect("No proposal with this ID");
+        require!(
+            proposal.state == ProposalState::Created,
+            "Proposal not in Created state - cannot post results"
+        );
+        require!(
+            proposal.weight_of(&worker_id).is_some(),
+            format!("Worker {} is not eligible for proposal #{}", worker_id, proposal_id)
+        );
+        require!(
+            !proposal.bound_results.iter().any(|b| b.worker_id == worker_id),
+            format!("Worker {} already posted a result for proposal #{}", worker_id, proposal_id)
+        );
+        proposal.bound_results.push(BoundResult {
+            worker_id: worker_id.clone(),
+            result_hash,
+        });
+        env::log_str(&format!(
+            "Worker {} posted its result for proposal #{}",
+            worker_id, proposal_id
+        ));
+    }
+
     /// Record worker submissions on-chain (nullifier pattern)
     /// Each worker can only submit once per proposal (prevents double-spending)
     pub fn record_worker_submissions(
@@ -250,6 +1148,7 @@ impl CoordinatorContract {
         proposal_id: u64,
         submissions: Vec<WorkerSubmissionInput>,
     ) {
+        self.require_not_paused();
         self.require_approved_codehash();
 
         let proposal = self
@@ -286,19 +1185,66 @@ impl CoordinatorContract {
                 )
             );
 
-            proposal.worker_submissions.push(WorkerSubmission {
-                worker_id: sub.worker_id,
+            // Errored workers produced no result to vouch for
+            if self.require_worker_binding && sub.status != SubmissionStatus::Errored {
+                let bound = self
+                    .registered_workers
+                    .get(&sub.worker_id)
+                    .filter(|w| w.active)
+                    .and_then(|w| w.account_id.as_ref());
+                require!(
+                    bound.is_some(),
+                    format!("Worker {} has no bound account", sub.worker_id)
+                );
+                let posted = proposal.bound_results.iter().any(|b| {
+                    b.worker_id == sub.worker_id && b.result_hash == sub.result_hash
+                });
+                require!(
+                    posted,
+                    format!(
+                        "Worker {} did not post this result from its bound account",
+                        sub.worker_id
+                    )
+                );
+            }
+
+            let weight = proposal.weight_of(&sub.worker_id).unwrap_or_else(|| {
+                env::panic_str(&format!(
+                    "Worker {} is not eligible for proposal #{}",
+                    sub.worker_id, proposal_id
+                ))
+            });
+            match sub.status {
+                SubmissionStatus::Voted => proposal.participating_weight += weight as u64,
+                SubmissionStatus::Abstained => {
+                    proposal.participating_weight += weight as u64;
+                    proposal.abstained_weight += weight as u64;
+                }
+                SubmissionStatus::Errored => {}
+            }
+
+            proposal.worker_submissions.push(WorkerSubmission {
+                worker_id: sub.worker_id,
                 result_hash: sub.result_hash,
                 timestamp: env::block_timestamp(),
+                status: sub.status,
             });
         }
 
         proposal.state = ProposalState::WorkersCompleted;
 
+        let errored = proposal
+            .worker_submissions
+            .iter()
+            .filter(|s| s.status == SubmissionStatus::Errored)
+            .count();
         env::log_str(&format!(
-            "Recorded {} worker submissions for proposal #{}",
+            "Recorded {} worker submissions for proposal #{} (weight {} of {}, {} errored)",
             proposal.worker_submissions.len(),
-            proposal_id
+            proposal_id,
+            proposal.participating_weight,
+            proposal.total_weight,
+            errored
         ));
     }
 
@@ -310,11 +1256,18 @@ impl CoordinatorContract {
         config_hash: String,
         result_hash: String,
     ) {
+        self.require_not_paused();
         self.require_approved_codehash();
+        let coordinator = env::predecessor_account_id();
+        let min_bond = self.challenge_config.min_coordinator_bond;
+        require!(
+            self.get_coordinator_bond(coordinator.clone()) >= min_bond,
+            format!("Coordinator bond below the required {}", min_bond.exact_amount_display())
+        );
 
         let proposal = self
             .proposals
-            .get(&proposal_id)
+            .get_mut(&proposal_id)
             .expect("No proposal with this ID");
 
         require!(
@@ -322,6 +1275,14 @@ impl CoordinatorContract {
             "Proposal not in WorkersCompleted state - record worker submissions first"
         );
 
+        require!(
+            proposal.has_participation_quorum(),
+            format!(
+                "Participation of {} is below quorum {}",
+                proposal.participating_weight, proposal.quorum
+            )
+        );
+
         require!(
             proposal.config_hash == config_hash,
             "Config hash mismatch - configuration was tampered with"
@@ -332,12 +1293,22 @@ impl CoordinatorContract {
             computed_hash == result_hash,
             "Result hash mismatch - result integrity check failed"
         );
+        match &proposal.options {
+            Some(options) => {
+                let winner =
+                    resolve_options(options, proposal.voting_weight(), &aggregated_result)
+                        .unwrap_or_else(|e| env::panic_str(&e));
+                env::log_str(&format!("Proposal #{} winning option: {}", proposal_id, winner));
+            }
+            None => validate_weighted_tally(proposal, &aggregated_result),
+        }
 
         env::log_str(&format!(
             "Coordinator resuming proposal #{} with result (length: {})",
             proposal_id,
             aggregated_result.len()
         ));
+        proposal.resolved_by = Some(coordinator);
 
         env::promise_yield_resume(
             &proposal.yield_id,
@@ -364,7 +1335,22 @@ impl CoordinatorContract {
 
                 if let Some(proposal) = self.proposals.get_mut(&proposal_id) {
                     proposal.state = ProposalState::Finalized;
+                    // Already validated by `coordinator_resume`
+                    proposal.winning_option = proposal.options.as_ref().and_then(|options| {
+                        resolve_options(options, proposal.voting_weight(), &result).ok()
+                    });
                     proposal.finalized_result = Some(result.clone());
+                    let period_ns = self.challenge_config.period_ns;
+                    if period_ns > 0 {
+                        let deadline = env::block_timestamp().saturating_add(period_ns);
+                        proposal.challenge_deadline = Some(deadline);
+                        if let Some(coordinator) = &proposal.resolved_by {
+                            let mut lock =
+                                self.bond_locks.get(coordinator).cloned().unwrap_or_default();
+                            lock.locked_until = lock.locked_until.max(deadline);
+                            self.bond_locks.insert(coordinator.clone(), lock);
+                        }
+                    }
                 }
 
                 PromiseOrValue::Value(result)
@@ -392,6 +1378,156 @@ impl CoordinatorContract {
         env::panic_str("Coordination request timed out");
     }
 
+    // ========== CHALLENGES ==========
+
+    /// Challenge a finalized result during its challenge period, pointing at
+    /// the worker submission it misreports. Attach `challenge_bond`; it is
+    /// refunded with the coordinator's slashed bond if the challenge is upheld.
+    #[payable]
+    pub fn challenge_result(&mut self, proposal_id: u64, worker_id: String, reason: String) -> u64 {
+        require!(!reason.is_empty(), "Challenge reason is required");
+        require!(reason.len() <= 1000, "Challenge reason needs to be under 1,000 characters");
+        let bond = env::attached_deposit();
+        require!(
+            bond >= self.challenge_config.challenge_bond,
+            format!(
+                "Challenge bond of {} required",
+                self.challenge_config.challenge_bond.exact_amount_display()
+            )
+        );
+        let proposal = self
+            .proposals
+            .get_mut(&proposal_id)
+            .expect("No proposal with this ID");
+        require!(
+            matches!(proposal.state, ProposalState::Finalized | ProposalState::Disputed),
+            "Only finalized results can be challenged"
+        );
+        let deadline = proposal
+            .challenge_deadline
+            .unwrap_or_else(|| env::panic_str("Proposal has no challenge period"));
+        require!(
+            env::block_timestamp() <= deadline,
+            format!("Challenge period for proposal #{} has ended", proposal_id)
+        );
+        require!(
+            proposal.worker_submissions.iter().any(|s| s.worker_id == worker_id),
+            format!("Worker {} has no submission for proposal #{}", worker_id, proposal_id)
+        );
+        if proposal.state == ProposalState::Finalized {
+            if let Some(coordinator) = &proposal.resolved_by {
+                let mut lock = self.bond_locks.get(coordinator).cloned().unwrap_or_default();
+                lock.open_disputes += 1;
+                self.bond_locks.insert(coordinator.clone(), lock);
+            }
+        }
+        proposal.state = ProposalState::Disputed;
+
+        let id = self.next_challenge_id;
+        self.next_challenge_id += 1;
+        let challenger = env::predecessor_account_id();
+        env::log_str(&format!(
+            "Challenge #{} by {} against proposal #{} (worker {})",
+            id, challenger, proposal_id, worker_id
+        ));
+        self.challenges.insert(
+            id,
+            Challenge {
+                id,
+                proposal_id,
+                worker_id,
+                reason,
+                challenger,
+                bond,
+                created_at: env::block_timestamp(),
+                status: ChallengeStatus::Pending,
+            },
+        );
+        id
+    }
+
+    /// Uphold or dismiss a pending challenge. Owner only. An upheld challenge
+    /// overturns the result and pays up to `min_coordinator_bond` of the
+    /// coordinator's bond to the challenger; a dismissed challenger's bond
+    /// goes to the coordinator.
+    pub fn resolve_challenge(&mut self, challenge_id: u64, upheld: bool) {
+        self.require_owner_action();
+        self.internal_resolve_challenge(challenge_id, upheld);
+    }
+
+    /// Set the challenge period and bonds. Owner only.
+    /// Applies to results finalized afterwards.
+    pub fn set_challenge_config(&mut self, config: ChallengeConfig) {
+        self.require_owner_action();
+        self.internal_set_challenge_config(config);
+    }
+
+    /// Add to the calling coordinator's bond
+    #[payable]
+    pub fn deposit_coordinator_bond(&mut self) {
+        let coordinator = env::predecessor_account_id();
+        require!(
+            self.coordinator_by_account_id.contains_key(&coordinator),
+            "Only registered coordinators can post a bond"
+        );
+        let bond = self
+            .get_coordinator_bond(coordinator.clone())
+            .saturating_add(env::attached_deposit());
+        self.coordinator_bonds.insert(coordinator.clone(), bond);
+        env::log_str(&format!(
+            "Coordinator {} bond is now {}",
+            coordinator,
+            bond.exact_amount_display()
+        ));
+    }
+
+    /// Withdraw from the calling coordinator's bond. Blocked while a result it
+    /// settled can still be challenged or has a pending challenge.
+    pub fn withdraw_coordinator_bond(&mut self, amount: NearToken) -> Promise {
+        let coordinator = env::predecessor_account_id();
+        let bond = self.get_coordinator_bond(coordinator.clone());
+        require!(amount <= bond, "Amount exceeds the coordinator bond");
+        let lock = self.get_coordinator_bond_lock(coordinator.clone());
+        require!(
+            lock.open_disputes == 0 && env::block_timestamp() > lock.locked_until,
+            "Bond is locked while settled results can be challenged"
+        );
+        let remaining = bond.saturating_sub(amount);
+        if remaining.is_zero() {
+            self.coordinator_bonds.remove(&coordinator);
+        } else {
+            self.coordinator_bonds.insert(coordinator.clone(), remaining);
+        }
+        Promise::new(coordinator).transfer(amount)
+    }
+
+    pub fn get_challenge(&self, challenge_id: u64) -> Option<Challenge> {
+        self.challenges.get(&challenge_id).cloned()
+    }
+
+    pub fn get_challenges(&self, proposal_id: u64) -> Vec<Challenge> {
+        self.challenges
+            .values()
+            .filter(|c| c.proposal_id == proposal_id)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_challenge_config(&self) -> ChallengeConfig {
+        self.challenge_config.clone()
+    }
+
+    pub fn get_coordinator_bond(&self, account_id: AccountId) -> NearToken {
+        self.coordinator_bonds
+            .get(&account_id)
+            .copied()
+            .unwrap_or(NearToken::from_near(0))
+    }
+
+    pub fn get_coordinator_bond_lock(&self, account_id: AccountId) -> BondLock {
+        self.bond_locks.get(&account_id).cloned().unwrap_or_default()
+    }
+
     // ========== VIEW FUNCTIONS ==========
 
     pub fn get_proposal(&self, proposal_id: u64) -> Option<Proposal> {
@@ -495,6 +1631,22 @@ impl CoordinatorContract {
             .count() as u32
     }
 
+    /// Combined weight of all active workers, i.e. of the next proposal
+    pub fn get_total_eligible_weight(&self) -> u64 {
+        self.registered_workers
+            .values()
+            .filter(|w| w.active)
+            .map(|w| w.weight as u64)
+            .sum()
+    }
+
+    /// Weight of the workers that voted or abstained on a proposal so far
+    pub fn get_proposal_participating_weight(&self, proposal_id: u64) -> Option<u64> {
+        self.proposals
+            .get(&proposal_id)
+            .map(|p| p.participating_weight)
+    }
+
     pub fn get_owner(&self) -> AccountId {
         self.owner.clone()
     }
@@ -503,19 +1655,65 @@ impl CoordinatorContract {
         self.current_proposal_id
     }
 
+    /// Get the `(min_workers, max_workers)` bounds for proposals
+    pub fn get_worker_bounds(&self) -> (u8, u8) {
+        (self.min_workers, self.max_workers)
+    }
+
+    pub fn get_registry_contract_id(&self) -> Option<AccountId> {
+        self.registry_contract_id.clone()
+    }
+
+    pub fn get_factory_account_id(&self) -> Option<AccountId> {
+        self.factory_account_id.clone()
+    }
+
+    /// Account the factory accepts upgrade requests from: the owner, or this
+    /// contract itself (via a council `UpgradeCode` action) while a council is set
+    pub fn get_upgrade_authority(&self) -> AccountId {
+        if self.council.is_some() {
+            env::current_account_id()
+        } else {
+            self.owner.clone()
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    pub fn get_pause_info(&self) -> Option<PauseInfo> {
+        self.paused.clone()
+    }
+
     // ========== OWNER FUNCTIONS ==========
 
-    pub fn approve_codehash(&mut self, codehash: String) {
-        self.require_owner();
-        self.approved_codehashes.insert(codehash.clone());
-        env::log_str(&format!("Approved codehash: {}", codehash));
+    /// Approve a TEE image. `role` defaults to `Coordinator`; an approval
+    /// with `expires_at` lapses at that timestamp (nanoseconds).
+    pub fn approve_codehash(
+        &mut self,
+        codehash: String,
+        label: Option<String>,
+        role: Option<CodehashRole>,
+        expires_at: Option<u64>,
+    ) {
+        self.require_owner_action();
+        self.internal_approve_codehash(codehash, label, role, expires_at);
     }
 
-    pub fn register_coordinator(&mut self, checksum: String, codehash: String) {
-        self.require_owner();
+    /// Register the calling account as a coordinator. Called by the TEE agent
+    /// itself with its quote; see `parse_attestation` for what is checked.
+    pub fn register_coordinator(&mut self, attestation: Attestation) {
         let caller = env::predecessor_account_id();
         require!(
-            self.approved_codehashes.contains(&codehash),
+            !self.removed_coordinators.contains(&caller),
+            format!("Coordinator {} was unregistered and must be reinstated first", caller)
+        );
+        let checksum = parse_attestation(&attestation, &caller)
+            .unwrap_or_else(|e| env::panic_str(&e));
+        let codehash = attestation.codehash;
+        require!(
+            self.is_codehash_approved_for(&codehash, CodehashRole::Coordinator),
             "Codehash not approved. Owner must approve_codehash first."
         );
         let worker = Worker {
@@ -529,18 +1727,107 @@ impl CoordinatorContract {
         ));
     }
 
+    /// Remove a registered coordinator and bar it from registering again. Owner only.
+    pub fn unregister_coordinator(&mut self, account_id: AccountId) {
+        self.require_owner_action();
+        self.internal_unregister_coordinator(account_id);
+    }
+
+    fn internal_unregister_coordinator(&mut self, account_id: AccountId) {
+        require!(
+            self.coordinator_by_account_id.remove(&account_id).is_some(),
+            format!("Coordinator {} not found", account_id)
+        );
+        self.removed_coordinators.insert(account_id.clone());
+        env::log_str(&format!("Coordinator {} unregistered", account_id));
+    }
+
+    /// Lift the bar `unregister_coordinator` placed on `account_id`. Owner only.
+    pub fn reinstate_coordinator(&mut self, account_id: AccountId) {
+        self.require_owner_action();
+        self.internal_reinstate_coordinator(account_id);
+    }
+
+    fn internal_reinstate_coordinator(&mut self, account_id: AccountId) {
+        require!(
+            self.removed_coordinators.remove(&account_id),
+            format!("Coordinator {} is not barred", account_id)
+        );
+        env::log_str(&format!("Coordinator {} reinstated", account_id));
+    }
+
+    /// Whether `account_id` was unregistered and is barred from registering again
+    pub fn is_coordinator_removed(&self, account_id: AccountId) -> bool {
+        self.removed_coordinators.contains(&account_id)
+    }
+
+    pub fn get_coordinator(&self, account_id: AccountId) -> Option<Worker> {
+        self.coordinator_by_account_id.get(&account_id).cloned()
+    }
+
+    pub fn get_coordinators(&self) -> Vec<(AccountId, Worker)> {
+        self.coordinator_by_account_id
+            .iter()
+            .map(|(account_id, worker)| (account_id.clone(), worker.clone()))
+            .collect()
+    }
+
     pub fn remove_codehash(&mut self, codehash: String) {
-        self.require_owner();
+        self.require_owner_action();
+        self.internal_remove_codehash(codehash);
+    }
+
+    fn internal_remove_codehash(&mut self, codehash: String) {
         self.approved_codehashes.remove(&codehash);
         env::log_str(&format!("Removed codehash: {}", codehash));
     }
 
+    /// Whether `codehash` is approved and not expired
     pub fn is_codehash_approved(&self, codehash: String) -> bool {
-        self.approved_codehashes.contains(&codehash)
+        self.approved_codehashes
+            .get(&codehash)
+            .is_some_and(|a| a.is_valid_at(env::block_timestamp()))
+    }
+
+    pub fn get_approved_codehash(&self, codehash: String) -> Option<ApprovedCodehash> {
+        self.approved_codehashes.get(&codehash).cloned()
+    }
+
+    /// Paginated approved codehashes with their metadata, including expired ones
+    pub fn list_approved_codehashes(
+        &self,
+        from_index: &Option<u64>,
+        limit: &Option<u64>,
+    ) -> Vec<ApprovedCodehash> {
+        let from = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(self.approved_codehashes.len() as u64);
+        self.approved_codehashes
+            .values()
+            .skip(from as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect()
     }
 
     pub fn clear_proposal(&mut self, proposal_id: u64) {
-        self.require_owner();
+        self.require_owner_action();
+        self.internal_clear_proposal(proposal_id);
+    }
+
+    fn internal_clear_proposal(&mut self, proposal_id: u64) {
+        // Clearing would drop the record a pending or possible challenge settles against
+        if let Some(proposal) = self.proposals.get(&proposal_id) {
+            require!(
+                proposal.state != ProposalState::Disputed,
+                format!("Proposal #{} has a pending challenge", proposal_id)
+            );
+            require!(
+                proposal
+                    .challenge_deadline
+                    .is_none_or(|deadline| env::block_timestamp() > deadline),
+                format!("Proposal #{} is still in its challenge period", proposal_id)
+            );
+        }
         self.proposals.remove(&proposal_id);
         env::log_str(&format!("Cleared proposal #{}", proposal_id));
     }
@@ -548,12 +1835,20 @@ impl CoordinatorContract {
     // ========== WORKER REGISTRATION ==========
 
     /// Register a worker that can participate in governance voting.
-    /// Only owner or an approved coordinator can call this.
+    /// Only owner or an approved coordinator can call this, and only for a
+    /// new `worker_id`.
     pub fn register_worker(&mut self, worker_id: String, account_id: Option<AccountId>) {
         let caller = env::predecessor_account_id();
+        if !self.coordinator_by_account_id.contains_key(&caller) {
+            require!(
+                caller == self.owner,
+                "Only owner or registered coordinator can register workers"
+            );
+            self.require_owner_action();
+        }
         require!(
-            caller == self.owner || self.coordinator_by_account_id.contains_key(&caller),
-            "Only owner or registered coordinator can register workers"
+            !self.registered_workers.contains_key(&worker_id),
+            format!("Worker {} is already registered", worker_id)
         );
 
         let worker = RegisteredWorker {
@@ -562,44 +1857,243 @@ impl CoordinatorContract {
             registered_at: env::block_timestamp(),
             registered_by: caller,
             active: true,
+            attestation: None,
+            weight: DEFAULT_WORKER_WEIGHT,
         };
         self.registered_workers.insert(worker_id.clone(), worker);
         env::log_str(&format!("Registered worker: {}", worker_id));
     }
 
+    /// Self-register a worker agent bound to the calling account, or attest a
+    /// worker already registered for it. The quote must bind the calling
+    /// account and its measurement must be an approved worker codehash; see
+    /// `parse_attestation` for what is checked.
+    pub fn register_worker_with_attestation(
+        &mut self,
+        worker_id: String,
+        attestation: Attestation,
+    ) {
+        let caller = env::predecessor_account_id();
+        let checksum = parse_attestation(&attestation, &caller)
+            .unwrap_or_else(|e| env::panic_str(&e));
+        require!(
+            self.is_codehash_approved_for(&attestation.codehash, CodehashRole::Worker),
+            "Worker codehash not approved. Owner must approve_codehash with the Worker role."
+        );
+        let attestation = Some(Worker {
+            checksum,
+            codehash: attestation.codehash,
+        });
+        if let Some(worker) = self.registered_workers.get_mut(&worker_id) {
+            require!(
+                worker.account_id.as_ref() == Some(&caller),
+                format!("Worker {} is registered to another account", worker_id)
+            );
+            require!(worker.active, format!("Worker {} is deactivated", worker_id));
+            worker.attestation = attestation;
+            env::log_str(&format!("Worker {} attested, bound to {}", worker_id, caller));
+            return;
+        }
+
+        let worker = RegisteredWorker {
+            worker_id: worker_id.clone(),
+            account_id: Some(caller.clone()),
+            registered_at: env::block_timestamp(),
+            registered_by: caller.clone(),
+            active: true,
+            attestation,
+            weight: DEFAULT_WORKER_WEIGHT,
+        };
+        self.registered_workers.insert(worker_id.clone(), worker);
+        env::log_str(&format!(
+            "Worker {} self-registered with attestation, bound to {}",
+            worker_id, caller
+        ));
+    }
+
+    /// Only record results that workers posted from their bound account. Owner only.
+    pub fn set_require_worker_binding(&mut self, required: bool) {
+        self.require_owner_action();
+        self.internal_set_require_worker_binding(required);
+    }
+
+    fn internal_set_require_worker_binding(&mut self, required: bool) {
+        self.require_worker_binding = required;
+        env::log_str(&format!("Worker binding required: {}", required));
+    }
+
+    pub fn get_require_worker_binding(&self) -> bool {
+        self.require_worker_binding
+    }
+
+    /// Accept raw `task_config` strings that are only hashed, not validated.
+    /// Multi-option proposals need a typed config. Owner only.
+    pub fn set_legacy_task_config(&mut self, enabled: bool) {
+        self.require_owner_action();
+        self.internal_set_legacy_task_config(enabled);
+    }
+
+    fn internal_set_legacy_task_config(&mut self, enabled: bool) {
+        self.legacy_task_config = enabled;
+        env::log_str(&format!("Legacy task config: {}", enabled));
+    }
+
+    pub fn get_legacy_task_config(&self) -> bool {
+        self.legacy_task_config
+    }
+
+    /// Set a worker's voting weight (at least 1). Owner only.
+    /// Proposals already created keep the weights they snapshotted.
+    pub fn set_worker_weight(&mut self, worker_id: String, weight: u32) {
+        self.require_owner_action();
+        self.internal_set_worker_weight(worker_id, weight);
+    }
+
     /// Remove a worker from the registry. Owner only.
     pub fn remove_worker(&mut self, worker_id: String) {
-        self.require_owner();
-        self.registered_workers.remove(&worker_id);
-        env::log_str(&format!("Removed worker: {}", worker_id));
+        self.require_owner_action();
+        self.internal_remove_worker(worker_id);
     }
 
     /// Deactivate a worker (keeps registration but prevents participation)
     pub fn deactivate_worker(&mut self, worker_id: String) {
-        self.require_owner();
-        if let Some(worker) = self.registered_workers.get_mut(&worker_id) {
-            worker.active = false;
-            env::log_str(&format!("Deactivated worker: {}", worker_id));
-        } else {
-            env::panic_str(&format!("Worker {} not found", worker_id));
-        }
+        self.require_owner_action();
+        self.internal_set_worker_active(worker_id, false);
     }
 
     /// Reactivate a previously deactivated worker
     pub fn activate_worker(&mut self, worker_id: String) {
-        self.require_owner();
-        if let Some(worker) = self.registered_workers.get_mut(&worker_id) {
-            worker.active = true;
-            env::log_str(&format!("Activated worker: {}", worker_id));
-        } else {
-            env::panic_str(&format!("Worker {} not found", worker_id));
+        self.require_owner_action();
+        self.internal_set_worker_active(worker_id, true);
+    }
+
+    /// Freeze coordination (e.g. a compromised coordinator key or TEE image).
+    /// Blocks `start_coordination`, `record_worker_submissions` and
+    /// `coordinator_resume`; proposals already in flight can only time out.
+    pub fn pause(&mut self, reason: String) {
+        self.require_owner_action();
+        self.internal_pause(reason);
+    }
+
+    pub fn unpause(&mut self) {
+        self.require_owner_action();
+        self.internal_unpause();
+    }
+
+    /// Propose a new owner. Takes effect once they call `accept_ownership`.
+    pub fn propose_owner(&mut self, new_owner: AccountId) {
+        self.require_owner_action();
+        self.internal_propose_owner(new_owner);
+    }
+
+    /// Accept a pending ownership transfer (pending owner only)
+    pub fn accept_ownership(&mut self) {
+        let caller = env::predecessor_account_id();
+        require!(
+            self.pending_owner.as_ref() == Some(&caller),
+            "Only the pending owner can accept"
+        );
+        self.owner = caller.clone();
+        self.pending_owner = None;
+        env::log_str(&format!("Ownership transferred to: {}", caller));
+    }
+
+    pub fn cancel_ownership_transfer(&mut self) {
+        self.require_owner_action();
+        self.internal_cancel_ownership_transfer();
+    }
+
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner.clone()
+    }
+
+    // ========== COUNCIL ==========
+
+    /// Enable M-of-N council governance. Owner only, and only while no council
+    /// is configured; afterwards the council changes itself via `SetCouncil`.
+    pub fn set_council(&mut self, members: Vec<AccountId>, threshold: u8) {
+        self.require_owner_action();
+        let council = Council { members, threshold };
+        validate_council(&council);
+        self.internal_set_council(Some(council));
+    }
+
+    /// Propose a council action (council member only). The proposer's
+    /// confirmation counts, so a threshold of 1 executes immediately.
+    pub fn propose_council_action(&mut self, action: CouncilAction) -> u64 {
+        let caller = self.require_council_member();
+        validate_council_action(&action);
+        let id = self.next_council_proposal_id;
+        self.next_council_proposal_id += 1;
+        self.council_proposals.insert(
+            id,
+            CouncilProposal {
+                id,
+                action,
+                proposed_by: caller.clone(),
+                confirmations: Vec::new(),
+                created_at: env::block_timestamp(),
+            },
+        );
+        env::log_str(&format!("Council proposal #{} created by {}", id, caller));
+        self.confirm_council_action(id);
+        id
+    }
+
+    /// Confirm a council action (council member only). Executes the action
+    /// once the confirmations reach the council threshold.
+    pub fn confirm_council_action(&mut self, proposal_id: u64) {
+        let caller = self.require_council_member();
+        let threshold = self.council.as_ref().map(|c| c.threshold).unwrap_or_default();
+        let proposal = self
+            .council_proposals
+            .get_mut(&proposal_id)
+            .unwrap_or_else(|| env::panic_str("No council proposal with this ID"));
+        require!(
+            !proposal.confirmations.contains(&caller),
+            "Already confirmed this council proposal"
+        );
+        proposal.confirmations.push(caller.clone());
+        env::log_str(&format!(
+            "Council proposal #{} confirmed by {} ({}/{})",
+            proposal_id,
+            caller,
+            proposal.confirmations.len(),
+            threshold
+        ));
+        if proposal.confirmations.len() >= threshold as usize {
+            let action = proposal.action.clone();
+            self.council_proposals.remove(&proposal_id);
+            self.execute_council_action(action);
+            env::log_str(&format!("Council proposal #{} executed", proposal_id));
         }
     }
 
-    pub fn transfer_ownership(&mut self, new_owner: AccountId) {
-        self.require_owner();
-        self.owner = new_owner.clone();
-        env::log_str(&format!("Ownership transferred to: {}", new_owner));
+    /// Withdraw a pending council action (its proposer only)
+    pub fn cancel_council_action(&mut self, proposal_id: u64) {
+        let caller = env::predecessor_account_id();
+        let proposal = self
+            .council_proposals
+            .get(&proposal_id)
+            .unwrap_or_else(|| env::panic_str("No council proposal with this ID"));
+        require!(
+            proposal.proposed_by == caller,
+            "Only the proposer can cancel a council proposal"
+        );
+        self.council_proposals.remove(&proposal_id);
+        env::log_str(&format!("Council proposal #{} cancelled", proposal_id));
+    }
+
+    pub fn get_council(&self) -> Option<Council> {
+        self.council.clone()
+    }
+
+    pub fn get_council_proposal(&self, proposal_id: u64) -> Option<CouncilProposal> {
+        self.council_proposals.get(&proposal_id).cloned()
+    }
+
+    pub fn get_council_proposals(&self) -> Vec<CouncilProposal> {
+        self.council_proposals.values().cloned().collect()
     }
 
     // ========== INTERNAL FUNCTIONS ==========
@@ -615,199 +2109,2722 @@ impl CoordinatorContract {
         );
     }
 
-    fn require_approved_codehash(&self) {
-        let caller = env::predecessor_account_id();
-        let worker = self
-            .coordinator_by_account_id
-            .get(&caller)
-            .expect(&format!(
-                "Only registered coordinator can call this function. Caller: {}",
-                caller
-            ));
+    /// Owner check for actions that become council proposals when a council is set
+    fn require_owner_action(&self) {
+        self.require_owner();
         require!(
-            self.approved_codehashes.contains(&worker.codehash),
-            "Coordinator codehash is no longer approved"
+            self.council.is_none(),
+            "Council governance is enabled. Use propose_council_action."
         );
     }
-}
 
-fn hash(data: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(data.as_bytes());
-    encode(hasher.finalize())
-}
+    fn require_council_member(&self) -> AccountId {
+        let caller = env::predecessor_account_id();
+        let is_member = self
+            .council
+            .as_ref()
+            .is_some_and(|c| c.members.contains(&caller));
+        require!(is_member, "Only council members can call this function");
+        caller
+    }
 
-#[cfg(test)]
+    fn execute_council_action(&mut self, action: CouncilAction) {
+        match action {
+            CouncilAction::ProposeManifesto { manifesto_text } => {
+                self.internal_propose_manifesto(manifesto_text)
+            }
+            CouncilAction::CancelManifestoChange => self.internal_cancel_manifesto_change(),
+            CouncilAction::SetManifestoDelay { delay_ns } => {
+                self.internal_set_manifesto_delay(delay_ns)
+            }
+            CouncilAction::ApproveCodehash {
+                codehash,
+                label,
+                role,
+                expires_at,
+            } => self.internal_approve_codehash(codehash, label, role, expires_at),
+            CouncilAction::RemoveCodehash { codehash } => self.internal_remove_codehash(codehash),
+            CouncilAction::UnregisterCoordinator { account_id } => {
+                self.internal_unregister_coordinator(account_id)
+            }
+            CouncilAction::ReinstateCoordinator { account_id } => {
+                self.internal_reinstate_coordinator(account_id)
+            }
+            CouncilAction::ClearProposal { proposal_id } => {
+                self.internal_clear_proposal(proposal_id)
+            }
+            CouncilAction::RemoveWorker { worker_id } => self.internal_remove_worker(worker_id),
+            CouncilAction::SetWorkerActive { worker_id, active } => {
+                self.internal_set_worker_active(worker_id, active)
+            }
+            CouncilAction::SetWorkerWeight { worker_id, weight } => {
+                self.internal_set_worker_weight(worker_id, weight)
+            }
+            CouncilAction::SetRequireWorkerBinding { required } => {
+                self.internal_set_require_worker_binding(required)
+            }
+            CouncilAction::SetLegacyTaskConfig { enabled } => {
+                self.internal_set_legacy_task_config(enabled)
+            }
+            CouncilAction::Pause { reason } => self.internal_pause(reason),
+            CouncilAction::Unpause => self.internal_unpause(),
+            CouncilAction::ProposeOwner { new_owner } => self.internal_propose_owner(new_owner),
+            CouncilAction::CancelOwnershipTransfer => self.internal_cancel_ownership_transfer(),
+            CouncilAction::UpgradeCode { code_hash } => self.internal_request_upgrade(code_hash),
+            CouncilAction::SetCouncil { council } => self.internal_set_council(council),
+            CouncilAction::SetChallengeConfig { config } => {
+                self.internal_set_challenge_config(config)
+            }
+            CouncilAction::ResolveChallenge {
+                challenge_id,
+                upheld,
+            } => self.internal_resolve_challenge(challenge_id, upheld),
+        }
+    }
+
+    fn internal_propose_manifesto(&mut self, manifesto_text: String) {
+        self.activate_pending_manifesto();
+        require!(
+            self.pending_manifesto.is_none(),
+            "A manifesto change is already queued. Cancel it first."
+        );
+        let manifesto = build_manifesto(manifesto_text);
+        let now = env::block_timestamp();
+        let effective_at = now.saturating_add(self.manifesto_delay_ns);
+        env::log_str(&format!(
+            "Manifesto change queued (hash: {}, effective at {})",
+            manifesto.hash, effective_at
+        ));
+        self.pending_manifesto = Some(PendingManifesto {
+            manifesto,
+            proposed_at: now,
+            effective_at,
+        });
+    }
+
+    fn internal_cancel_manifesto_change(&mut self) {
+        self.activate_pending_manifesto();
+        let pending = self
+            .pending_manifesto
+            .take()
+            .unwrap_or_else(|| env::panic_str("No manifesto change queued"));
+        env::log_str(&format!(
+            "Cancelled manifesto change (hash: {})",
+            pending.manifesto.hash
+        ));
+    }
+
+    fn internal_set_manifesto_delay(&mut self, delay_ns: u64) {
+        validate_manifesto_delay(delay_ns);
+        self.manifesto_delay_ns = delay_ns;
+        env::log_str(&format!("Manifesto delay set to {} ns", delay_ns));
+    }
+
+    /// Promote the queued manifesto once its delay has passed
+    fn activate_pending_manifesto(&mut self) {
+        let due = self
+            .pending_manifesto
+            .as_ref()
+            .is_some_and(|p| p.effective_at <= env::block_timestamp());
+        if due {
+            let pending = self.pending_manifesto.take().unwrap();
+            env::log_str(&format!("Manifesto activated (hash: {})", pending.manifesto.hash));
+            self.manifesto = Some(pending.manifesto);
+        }
+    }
+
+    fn internal_approve_codehash(
+        &mut self,
+        codehash: String,
+        label: Option<String>,
+        role: Option<CodehashRole>,
+        expires_at: Option<u64>,
+    ) {
+        validate_codehash_approval(&label, expires_at);
+        let approved = ApprovedCodehash {
+            codehash: codehash.clone(),
+            label: label.unwrap_or_default(),
+            role: role.unwrap_or(CodehashRole::Coordinator),
+            approved_by: env::predecessor_account_id(),
+            approved_at: env::block_timestamp(),
+            expires_at,
+        };
+        env::log_str(&format!(
+            "Approved codehash: {} ({:?}, expires at {:?})",
+            codehash, approved.role, expires_at
+        ));
+        self.approved_codehashes.insert(codehash, approved);
+    }
+
+    fn is_codehash_approved_for(&self, codehash: &str, role: CodehashRole) -> bool {
+        self.approved_codehashes
+            .get(codehash)
+            .is_some_and(|a| a.role == role && a.is_valid_at(env::block_timestamp()))
+    }
+
+    fn internal_remove_worker(&mut self, worker_id: String) {
+        self.registered_workers.remove(&worker_id);
+        env::log_str(&format!("Removed worker: {}", worker_id));
+    }
+
+    fn internal_set_worker_weight(&mut self, worker_id: String, weight: u32) {
+        validate_worker_weight(weight);
+        let worker = self
+            .registered_workers
+            .get_mut(&worker_id)
+            .unwrap_or_else(|| env::panic_str(&format!("Worker {} not found", worker_id)));
+        worker.weight = weight;
+        env::log_str(&format!("Set weight of worker {} to {}", worker_id, weight));
+    }
+
+    fn snapshot_worker_weights(&self) -> Vec<WorkerWeight> {
+        self.registered_workers
+            .values()
+            .filter(|w| w.active)
+            .map(|w| WorkerWeight {
+                worker_id: w.worker_id.clone(),
+                weight: w.weight,
+            })
+            .collect()
+    }
+
+    /// Validate a `task_config` unless legacy mode is on, returning its options
+    fn task_config_options(
+        &self,
+        task_config: &str,
+        expected_worker_count: u8,
+        quorum: u8,
+    ) -> Option<ProposalOptions> {
+        if self.legacy_task_config {
+            return None;
+        }
+        let config: TaskConfig = serde_json::from_str(task_config)
+            .unwrap_or_else(|e| env::panic_str(&format!("Invalid task config: {}", e)));
+        validate_task_config(&config, expected_worker_count, quorum);
+        config.proposal_options()
+    }
+
+    fn internal_set_challenge_config(&mut self, config: ChallengeConfig) {
+        env::log_str(&format!(
+            "Challenge period set to {} ns, challenge bond {}, coordinator bond {}",
+            config.period_ns,
+            config.challenge_bond.exact_amount_display(),
+            config.min_coordinator_bond.exact_amount_display()
+        ));
+        self.challenge_config = config;
+    }
+
+    fn internal_resolve_challenge(&mut self, challenge_id: u64, upheld: bool) {
+        let mut challenge = self
+            .challenges
+            .get(&challenge_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str(&format!("Challenge #{} not found", challenge_id)));
+        require!(
+            challenge.status == ChallengeStatus::Pending,
+            format!("Challenge #{} is already resolved", challenge_id)
+        );
+        let others_pending = self.challenges.values().any(|c| {
+            c.proposal_id == challenge.proposal_id
+                && c.id != challenge_id
+                && c.status == ChallengeStatus::Pending
+        });
+        let proposal = self
+            .proposals
+            .get_mut(&challenge.proposal_id)
+            .expect("No proposal with this ID");
+        let was_disputed = proposal.state == ProposalState::Disputed;
+
+        if upheld {
+            challenge.status = ChallengeStatus::Upheld;
+            proposal.state = ProposalState::Overturned;
+            // The result is slashed once, by at most the bond needed to settle it
+            let mut slashed = NearToken::from_near(0);
+            if let Some(coordinator) = proposal.resolved_by.as_ref().filter(|_| was_disputed) {
+                let bond = self.coordinator_bonds.get(coordinator).copied().unwrap_or_default();
+                slashed = bond.min(self.challenge_config.min_coordinator_bond);
+                let remaining = bond.saturating_sub(slashed);
+                if remaining.is_zero() {
+                    self.coordinator_bonds.remove(coordinator);
+                } else {
+                    self.coordinator_bonds.insert(coordinator.clone(), remaining);
+                }
+            }
+            env::log_str(&format!(
+                "Challenge #{} upheld, proposal #{} overturned, slashed {}",
+                challenge_id,
+                challenge.proposal_id,
+                slashed.exact_amount_display()
+            ));
+            Promise::new(challenge.challenger.clone())
+                .transfer(challenge.bond.saturating_add(slashed));
+        } else {
+            challenge.status = ChallengeStatus::Dismissed;
+            if proposal.state == ProposalState::Disputed && !others_pending {
+                proposal.state = ProposalState::Finalized;
+            }
+            env::log_str(&format!("Challenge #{} dismissed", challenge_id));
+            if let Some(coordinator) = &proposal.resolved_by {
+                Promise::new(coordinator.clone()).transfer(challenge.bond);
+            }
+        }
+        if was_disputed && proposal.state != ProposalState::Disputed {
+            if let Some(coordinator) = &proposal.resolved_by {
+                if let Some(lock) = self.bond_locks.get_mut(coordinator) {
+                    lock.open_disputes = lock.open_disputes.saturating_sub(1);
+                }
+            }
+        }
+        self.challenges.insert(challenge_id, challenge);
+    }
+
+    fn internal_pause(&mut self, reason: String) {
+        validate_pause_reason(&reason);
+        env::log_str(&format!("Contract paused: {}", reason));
+        self.paused = Some(PauseInfo {
+            reason,
+            paused_at: env::block_timestamp(),
+        });
+    }
+
+    fn internal_unpause(&mut self) {
+        require!(self.paused.is_some(), "Contract is not paused");
+        require!(
+            self.legacy_collections.is_none(),
+            "State migration in progress, finish it with migrate_step"
+        );
+        self.paused = None;
+        env::log_str("Contract unpaused");
+    }
+
+    fn internal_propose_owner(&mut self, new_owner: AccountId) {
+        self.pending_owner = Some(new_owner.clone());
+        env::log_str(&format!("Proposed new owner: {}", new_owner));
+    }
+
+    fn internal_cancel_ownership_transfer(&mut self) {
+        self.pending_owner = None;
+        env::log_str("Cancelled pending ownership transfer");
+    }
+
+    fn internal_request_upgrade(&mut self, code_hash: Option<String>) {
+        let factory = self
+            .factory_account_id
+            .clone()
+            .unwrap_or_else(|| env::panic_str("Not deployed by a factory"));
+        let args = json!({
+            "account_id": env::current_account_id(),
+            "code_hash": code_hash,
+        })
+        .to_string()
+        .into_bytes();
+        Promise::new(factory.clone()).function_call(
+            "upgrade_coordinator".to_string(),
+            args,
+            NearToken::from_near(0),
+            REQUEST_UPGRADE_GAS,
+        );
+        env::log_str(&format!("Requested upgrade from factory {}", factory));
+    }
+
+    fn internal_set_worker_active(&mut self, worker_id: String, active: bool) {
+        let worker = self
+            .registered_workers
+            .get_mut(&worker_id)
+            .unwrap_or_else(|| env::panic_str(&format!("Worker {} not found", worker_id)));
+        worker.active = active;
+        if active {
+            env::log_str(&format!("Activated worker: {}", worker_id));
+        } else {
+            env::log_str(&format!("Deactivated worker: {}", worker_id));
+        }
+    }
+
+    /// Replacing the council drops pending proposals, which were confirmed
+    /// against the old membership.
+    fn internal_set_council(&mut self, council: Option<Council>) {
+        self.council_proposals.clear();
+        match &council {
+            Some(c) => env::log_str(&format!(
+                "Council set: {} of {} members",
+                c.threshold,
+                c.members.len()
+            )),
+            None => env::log_str("Council removed"),
+        }
+        self.council = council;
+    }
+
+    fn require_not_paused(&self) {
+        if let Some(pause) = &self.paused {
+            env::panic_str(&format!("Contract is paused: {}", pause.reason));
+        }
+    }
+
+    fn require_approved_codehash(&self) {
+        let caller = env::predecessor_account_id();
+        let worker = self
+            .coordinator_by_account_id
+            .get(&caller)
+            .unwrap_or_else(|| {
+                env::panic_str(&format!(
+                    "Only registered coordinator can call this function. Caller: {}",
+                    caller
+                ))
+            });
+        let approved = self
+            .approved_codehashes
+            .get(&worker.codehash)
+            .unwrap_or_else(|| env::panic_str("Coordinator codehash is no longer approved"));
+        require!(
+            approved.role == CodehashRole::Coordinator,
+            "Coordinator codehash is not approved for the Coordinator role"
+        );
+        require!(
+            approved.is_valid_at(env::block_timestamp()),
+            "Coordinator codehash approval has expired"
+        );
+    }
+}
+
+// ========== ATTESTATION ==========
+
+/// Decode the fields the contract relies on from a TDX v4 quote.
+/// The DCAP signature chain and collateral are not verified here.
+pub fn parse_tdx_quote(quote: &[u8]) -> Result<ParsedQuote, String> {
+    let body_end = QUOTE_HEADER_LEN + TD_REPORT_LEN;
+    if quote.len() < body_end + 4 {
+        return Err(format!("Quote too short: {} bytes", quote.len()));
+    }
+    let version = u16::from_le_bytes([quote[0], quote[1]]);
+    let tee_type = u32::from_le_bytes([quote[4], quote[5], quote[6], quote[7]]);
+    if version != TDX_QUOTE_VERSION || tee_type != TDX_TEE_TYPE {
+        return Err(format!(
+            "Unsupported quote: version {}, TEE type {:#x}",
+            version, tee_type
+        ));
+    }
+    let signature_len = u32::from_le_bytes([
+        quote[body_end],
+        quote[body_end + 1],
+        quote[body_end + 2],
+        quote[body_end + 3],
+    ]) as usize;
+    if quote.len() < body_end + 4 + signature_len {
+        return Err("Quote signature data is truncated".to_string());
+    }
+
+    let report = &quote[QUOTE_HEADER_LEN..body_end];
+    let mrtd = &report[TD_REPORT_MRTD_OFFSET..TD_REPORT_MRTD_OFFSET + MEASUREMENT_REGISTER_LEN];
+    let rtmrs = &report[TD_REPORT_RTMR_OFFSET..TD_REPORT_DATA_OFFSET];
+    let mut hasher = Sha256::new();
+    hasher.update(mrtd);
+    hasher.update(rtmrs);
+    let mut report_data = [0u8; 64];
+    report_data.copy_from_slice(&report[TD_REPORT_DATA_OFFSET..]);
+    Ok(ParsedQuote {
+        measurement: encode(hasher.finalize()),
+        report_data,
+    })
+}
+
+/// Parse the quote and check that its measurement is the claimed codehash and
+/// that its report_data commits to `account_id` (first 32 bytes = SHA-256 of
+/// the account ID; the rest is free for the agent's key). Returns the quote
+/// checksum (SHA-256 hex of the quote bytes).
+///
+/// This only parses: the DCAP signature chain, QE report and collateral are
+/// not verified, so anyone can build a quote that passes.
+pub fn parse_attestation(
+    attestation: &Attestation,
+    account_id: &AccountId,
+) -> Result<String, String> {
+    let quote = hex::decode(&attestation.quote_hex)
+        .map_err(|_| "Quote is not valid hex".to_string())?;
+    let parsed = parse_tdx_quote(&quote)?;
+    if parsed.measurement != attestation.codehash {
+        return Err(format!(
+            "Quote measurement {} does not match codehash {}",
+            parsed.measurement, attestation.codehash
+        ));
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(account_id.as_bytes());
+    if parsed.report_data[..32] != hasher.finalize()[..] {
+        return Err(format!("Quote report_data is not bound to {}", account_id));
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(&quote);
+    Ok(encode(hasher.finalize()))
+}
+
+fn validate_pause_reason(reason: &str) {
+    require!(!reason.is_empty(), "Pause reason is required");
+    require!(reason.len() <= 1000, "Pause reason needs to be under 1,000 characters");
+}
+
+fn validate_codehash_approval(label: &Option<String>, expires_at: Option<u64>) {
+    if let Some(label) = label {
+        require!(label.len() <= 256, "Codehash label needs to be under 256 characters");
+    }
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > env::block_timestamp(),
+            "Codehash expiry must be in the future"
+        );
+    }
+}
+
+fn validate_worker_weight(weight: u32) {
+    require!(weight >= 1, "Worker weight must be at least 1");
+}
+
+/// Check a vote tally (`approved`/`rejected` weights and `decision`) reported
+/// in the aggregated result against the proposal's weights. Abstentions do not
+/// count toward the decision. Results without a numeric tally are not checked,
+/// and the decision is not checked when no votes were cast: the agent reports
+/// non-vote tasks as `approved: 0, rejected: 0, decision: "Approved"`.
+fn validate_weighted_tally(proposal: &Proposal, aggregated_result: &str) {
+    let Ok(result) = serde_json::from_str::<serde_json::Value>(aggregated_result) else {
+        return;
+    };
+    let (Some(approved), Some(rejected)) = (
+        result.get("approved").and_then(|v| v.as_u64()),
+        result.get("rejected").and_then(|v| v.as_u64()),
+    ) else {
+        return;
+    };
+    let voting_weight = proposal.voting_weight();
+    require!(
+        approved.saturating_add(rejected) <= voting_weight,
+        format!(
+            "Tally of {} exceeds voting weight {}",
+            approved.saturating_add(rejected),
+            voting_weight
+        )
+    );
+    if let Some(abstained) = result.get("abstained").and_then(|v| v.as_u64()) {
+        require!(
+            abstained == proposal.abstained_weight,
+            format!(
+                "Tally reports {} abstained, recorded {}",
+                abstained, proposal.abstained_weight
+            )
+        );
+    }
+    if approved == 0 && rejected == 0 {
+        return;
+    }
+    if let Some(decision) = result.get("decision").and_then(|v| v.as_str()) {
+        // `quorum` approvals, or a strict majority of the voting weight
+        let threshold = if proposal.quorum > 0 {
+            proposal.quorum as u64
+        } else {
+            voting_weight / 2 + 1
+        };
+        let expected = if approved >= threshold { "Approved" } else { "Rejected" };
+        require!(
+            decision == expected,
+            format!("Decision {} does not match weighted tally", decision)
+        );
+    }
+}
+
+/// Check a task config and cross-check its `voting_config` against the
+/// worker count and quorum the proposal is created with
+fn validate_task_config(config: &TaskConfig, expected_worker_count: u8, quorum: u8) {
+    require!(
+        !config.task_type.is_empty() && config.task_type.len() <= 64,
+        "Task type needs to be 1 to 64 characters"
+    );
+    if config.task_type == "vote" {
+        let has_proposal = config
+            .parameters
+            .proposal
+            .as_ref()
+            .is_some_and(|p| !p.trim().is_empty());
+        require!(has_proposal, "Vote task requires parameters.proposal");
+    }
+    require!(config.timeout != Some(0), "Task timeout must be positive");
+    if let Some(metadata) = &config.metadata {
+        require!(metadata.is_object(), "Task metadata must be a JSON object");
+    }
+    let voting = config.parameters.voting_config.clone().unwrap_or_default();
+    if let Some(min_workers) = voting.min_workers {
+        require!(
+            expected_worker_count >= min_workers,
+            format!(
+                "expected_worker_count {} is below voting_config.min_workers {}",
+                expected_worker_count, min_workers
+            )
+        );
+    }
+    // The agent passes `voting_config.quorum`, or 0 for a majority
+    let config_quorum = voting.quorum.unwrap_or(0);
+    require!(
+        quorum == config_quorum,
+        format!(
+            "quorum {} does not match voting_config.quorum {}",
+            quorum, config_quorum
+        )
+    );
+    if let Some(options) = config.proposal_options() {
+        validate_proposal_options(&options);
+    }
+}
+
+// ========== MULTI-OPTION TALLY ==========
+
+fn validate_proposal_options(options: &ProposalOptions) {
+    require!(
+        options.options.len() >= 2 && options.options.len() <= MAX_PROPOSAL_OPTIONS,
+        format!("A proposal needs between 2 and {} options", MAX_PROPOSAL_OPTIONS)
+    );
+    for (i, option) in options.options.iter().enumerate() {
+        require!(
+            !option.is_empty() && option.len() <= MAX_OPTION_LEN,
+            format!("Options need to be 1 to {} characters", MAX_OPTION_LEN)
+        );
+        require!(
+            !options.options[..i].contains(option),
+            format!("Duplicate option {}", option)
+        );
+    }
+}
+
+/// Validate the option tally in an aggregated result against the declared
+/// options and the voting weight, and return the winning option.
+/// Ties go to the option declared first.
+pub fn resolve_options(
+    options: &ProposalOptions,
+    voting_weight: u64,
+    aggregated_result: &str,
+) -> Result<String, String> {
+    let tally: OptionTally = serde_json::from_str(aggregated_result)
+        .map_err(|e| format!("Aggregated result is not an option tally: {}", e))?;
+    let declared = &options.options;
+    let winner = match options.resolution {
+        ResolutionMode::Plurality | ResolutionMode::Approval => {
+            if let Some(unknown) = tally.tally.keys().find(|o| !declared.contains(o)) {
+                return Err(format!("Unknown option {}", unknown));
+            }
+            // A plurality voter backs one option, an approval voter may back all
+            let counted = if options.resolution == ResolutionMode::Plurality {
+                tally.tally.values().fold(0u64, |sum, w| sum.saturating_add(*w))
+            } else {
+                tally.tally.values().copied().max().unwrap_or(0)
+            };
+            if counted > voting_weight {
+                return Err(format!(
+                    "Tally of {} exceeds voting weight {}",
+                    counted, voting_weight
+                ));
+            }
+            let counts: Vec<u64> = declared
+                .iter()
+                .map(|o| tally.tally.get(o).copied().unwrap_or(0))
+                .collect();
+            match leading_index(&counts) {
+                Some(i) if counts[i] > 0 => declared[i].clone(),
+                _ => return Err("No votes for any option".to_string()),
+            }
+        }
+        ResolutionMode::InstantRunoff => {
+            instant_runoff(declared, voting_weight, &tally.ballots)?
+        }
+    };
+    if let Some(reported) = &tally.winner {
+        if *reported != winner {
+            return Err(format!(
+                "Reported winner {} does not match tally winner {}",
+                reported, winner
+            ));
+        }
+    }
+    Ok(winner)
+}
+
+/// Index of the highest count, the earliest one on ties
+fn leading_index(counts: &[u64]) -> Option<usize> {
+    counts
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, count)| **count)
+        .map(|(i, _)| i)
+}
+
+fn instant_runoff(
+    declared: &[String],
+    voting_weight: u64,
+    ballots: &[RankedBallot],
+) -> Result<String, String> {
+    let mut total = 0u64;
+    for ballot in ballots {
+        if ballot.ranking.is_empty() {
+            return Err("Ranked ballots need at least one option".to_string());
+        }
+        for (i, choice) in ballot.ranking.iter().enumerate() {
+            if !declared.contains(choice) {
+                return Err(format!("Unknown option {}", choice));
+            }
+            if ballot.ranking[..i].contains(choice) {
+                return Err(format!("Option {} is ranked twice on a ballot", choice));
+            }
+        }
+        total = total.saturating_add(ballot.weight);
+    }
+    if total > voting_weight {
+        return Err(format!(
+            "Tally of {} exceeds voting weight {}",
+            total, voting_weight
+        ));
+    }
+
+    let mut remaining: Vec<&String> = declared.iter().collect();
+    loop {
+        // Each ballot counts for its highest-ranked option still in the race
+        let mut counts = vec![0u64; remaining.len()];
+        for ballot in ballots {
+            let choice = ballot
+                .ranking
+                .iter()
+                .find_map(|choice| remaining.iter().position(|o| *o == choice));
+            if let Some(i) = choice {
+                counts[i] += ballot.weight;
+            }
+        }
+        let active: u64 = counts.iter().sum();
+        if active == 0 {
+            return Err("No votes for any option".to_string());
+        }
+        let leader = leading_index(&counts).unwrap_or(0);
+        if counts[leader] * 2 > active || remaining.len() == 1 {
+            return Ok(remaining[leader].clone());
+        }
+        // Eliminate the last-placed option, the latest declared on ties
+        let lowest = counts.iter().copied().min().unwrap_or(0);
+        if let Some(last) = counts.iter().rposition(|count| *count == lowest) {
+            remaining.remove(last);
+        }
+    }
+}
+
+fn validate_council(council: &Council) {
+    require!(!council.members.is_empty(), "Council needs at least one member");
+    let mut members = council.members.clone();
+    members.sort();
+    members.dedup();
+    require!(
+        members.len() == council.members.len(),
+        "Council members must be unique"
+    );
+    require!(
+        council.threshold >= 1 && council.threshold as usize <= council.members.len(),
+        "Council threshold must be between 1 and the number of members"
+    );
+}
+
+/// Reject invalid actions when proposed rather than when the last confirmation lands
+fn validate_council_action(action: &CouncilAction) {
+    match action {
+        CouncilAction::ProposeManifesto { manifesto_text } => require!(
+            manifesto_text.len() <= 10000,
+            "Manifesto text needs to be under 10,000 characters"
+        ),
+        CouncilAction::Pause { reason } => validate_pause_reason(reason),
+        CouncilAction::ApproveCodehash {
+            label, expires_at, ..
+        } => validate_codehash_approval(label, *expires_at),
+        CouncilAction::SetCouncil { council: Some(council) } => validate_council(council),
+        CouncilAction::SetWorkerWeight { weight, .. } => validate_worker_weight(*weight),
+        CouncilAction::SetManifestoDelay { delay_ns } => validate_manifesto_delay(*delay_ns),
+        _ => {}
+    }
+}
+
+fn validate_manifesto_delay(delay_ns: u64) {
+    require!(
+        delay_ns >= MIN_MANIFESTO_DELAY_NS,
+        format!("Manifesto delay must be at least {} ns", MIN_MANIFESTO_DELAY_NS)
+    );
+}
+
+fn build_manifesto(manifesto_text: String) -> Manifesto {
+    require!(
+        manifesto_text.len() <= 10000,
+        "Manifesto text needs to be under 10,000 characters"
+    );
+    let manifesto_hash = hash(&manifesto_text);
+    Manifesto {
+        text: manifesto_text,
+        hash: manifesto_hash,
+    }
+}
+
+fn hash(data: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data.as_bytes());
+    encode(hasher.finalize())
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
     use near_sdk::test_utils::{accounts, VMContextBuilder};
     use near_sdk::testing_env;
 
-    fn get_context(predecessor: AccountId) -> VMContextBuilder {
-        let mut builder = VMContextBuilder::new();
-        builder
-            .predecessor_account_id(predecessor.clone())
-            .signer_account_id(predecessor)
-            .attached_deposit(NearToken::from_near(0));
-        builder
+    // Synthetic TDX quotes, see tests/fixtures/generate.py
+    const ALICE_QUOTE: &str = include_str!("../tests/fixtures/tdx_v4_alice.hex");
+    const BOB_QUOTE: &str = include_str!("../tests/fixtures/tdx_v4_bob.hex");
+    const TRUNCATED_QUOTE: &str = include_str!("../tests/fixtures/tdx_v4_truncated.hex");
+    const BAD_SIGNATURE_LEN_QUOTE: &str =
+        include_str!("../tests/fixtures/tdx_v4_bad_signature_len.hex");
+    const SGX_QUOTE: &str = include_str!("../tests/fixtures/sgx_v3.hex");
+    const TEST_MEASUREMENT: &str =
+        "1868a6647f293d679caa2abac852691d8f374210bfe0c78bc577fd834ed6a226";
+
+    fn fixture_attestation(quote: &str) -> Attestation {
+        Attestation {
+            quote_hex: quote.trim().to_string(),
+            codehash: TEST_MEASUREMENT.to_string(),
+        }
+    }
+
+    /// Proposal as `start_coordination` creates it, with no registered workers
+    fn test_proposal(expected_worker_count: u8, quorum: u8) -> Proposal {
+        Proposal {
+            yield_id: CryptoHash::default(),
+            task_config: "test".to_string(),
+            config_hash: hash("test"),
+            timestamp: 0,
+            requester: accounts(0),
+            state: ProposalState::Created,
+            expected_worker_count,
+            quorum,
+            worker_submissions: Vec::new(),
+            finalized_result: None,
+            worker_weights: Vec::new(),
+            total_weight: expected_worker_count as u64,
+            participating_weight: 0,
+            abstained_weight: 0,
+            options: None,
+            winning_option: None,
+            resolved_by: None,
+            challenge_deadline: None,
+            manifesto_hash: None,
+            bound_results: Vec::new(),
+        }
+    }
+
+    fn get_context(predecessor: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .predecessor_account_id(predecessor.clone())
+            .signer_account_id(predecessor)
+            .attached_deposit(NearToken::from_near(0));
+        builder
+    }
+
+    // ========== TEST SETUP ==========
+
+    fn owner_contract() -> CoordinatorContract {
+        testing_env!(get_context(accounts(0)).build());
+        CoordinatorContract::new(accounts(0))
+    }
+
+    /// Alice as coordinator, workers with weights 3 and 1 and proposal #1 snapshotting them
+    fn contract_with_proposal() -> CoordinatorContract {
+        let mut contract = owner_contract();
+        contract.set_manifesto("We vote for good things.".to_string());
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        contract.register_coordinator(fixture_attestation(ALICE_QUOTE));
+        contract.register_worker("worker1".to_string(), None);
+        contract.register_worker("worker2".to_string(), None);
+        contract.set_worker_weight("worker1".to_string(), 3);
+        let mut proposal = test_proposal(2, 0);
+        proposal.worker_weights = contract.snapshot_worker_weights();
+        proposal.total_weight = contract.get_total_eligible_weight();
+        contract.proposals.insert(1, proposal);
+        contract.current_proposal_id = 1;
+        contract
+    }
+
+    /// Council of bob, charlie and danny with a 2-of-3 threshold
+    fn add_council(contract: &mut CoordinatorContract) {
+        contract.set_council(vec![accounts(1), accounts(2), accounts(3)], 2);
+    }
+
+    /// Approve the worker codehash and register `worker1` for bob
+    fn add_worker_codehash(contract: &mut CoordinatorContract) {
+        contract.approve_codehash(
+            TEST_MEASUREMENT.to_string(),
+            Some("worker".to_string()),
+            Some(CodehashRole::Worker),
+            None,
+        );
+        contract.register_worker("worker1".to_string(), Some(accounts(1)));
+    }
+
+    /// Require binding, with bob's attested `worker1` and proposal #1 expecting one worker
+    fn require_binding(contract: &mut CoordinatorContract) {
+        add_worker_codehash(contract);
+        contract.set_require_worker_binding(true);
+        contract.approve_codehash(
+            "coordinator_hash".to_string(),
+            None,
+            Some(CodehashRole::Coordinator),
+            None,
+        );
+        // Register the coordinator directly; attestation is covered separately
+        contract.coordinator_by_account_id.insert(
+            accounts(0),
+            Worker {
+                checksum: "checksum".to_string(),
+                codehash: "coordinator_hash".to_string(),
+            },
+        );
+        testing_env!(get_context(accounts(1)).build());
+        self_register(contract, "worker1", BOB_QUOTE);
+        testing_env!(get_context(accounts(0)).build());
+        contract.proposals.insert(1, test_proposal(1, 1));
+    }
+
+    /// Settle proposal #1 by the coordinator (alice, 5 NEAR bond) at t=10
+    /// with a 100ns challenge period
+    fn settle_proposal(contract: &mut CoordinatorContract) {
+        contract.set_challenge_config(ChallengeConfig {
+            period_ns: 100,
+            challenge_bond: NearToken::from_near(1),
+            min_coordinator_bond: NearToken::from_near(5),
+        });
+        testing_env!(context_at(accounts(0), 0).attached_deposit(NearToken::from_near(5)).build());
+        contract.deposit_coordinator_bond();
+        contract.record_worker_submissions(1, vec![submission("worker1"), submission("worker2")]);
+        // Set by `coordinator_resume`, which needs a real yield
+        contract.proposals.get_mut(&1).unwrap().resolved_by = Some(accounts(0));
+        testing_env!(context_at(accounts(0), 10).build());
+        let result = r#"{"approved":3,"rejected":1,"decision":"Approved"}"#.to_string();
+        contract.return_coordination_result(1, "test".to_string(), Ok(result));
+    }
+
+    /// Settle proposal #1 and have charlie challenge worker1's vote at t=20
+    fn open_challenge(contract: &mut CoordinatorContract) {
+        settle_proposal(contract);
+        testing_env!(context_at(accounts(2), 20).attached_deposit(NearToken::from_near(1)).build());
+        let reason = "Vote misreported".to_string();
+        let id = contract.challenge_result(1, "worker1".to_string(), reason);
+        assert_eq!(id, 0);
+    }
+
+    #[test]
+    fn test_initialization() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let contract = CoordinatorContract::new(accounts(0));
+        assert_eq!(contract.get_owner(), accounts(0));
+        assert_eq!(contract.get_current_proposal_id(), 0);
+        assert!(contract.get_manifesto().is_none());
+    }
+
+    #[test]
+    fn test_set_manifesto() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto("We vote for good things.".to_string());
+        let manifesto = contract.get_manifesto().unwrap();
+        assert_eq!(manifesto.text, "We vote for good things.");
+        assert_eq!(manifesto.hash.len(), 64);
+    }
+
+    #[test]
+    fn test_approve_codehash() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash("test_codehash".to_string(), None, None, None);
+        assert!(contract.is_codehash_approved("test_codehash".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_non_owner_cannot_approve_codehash() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        testing_env!(get_context(accounts(1)).build());
+        contract.approve_codehash("test_codehash".to_string(), None, None, None);
+    }
+
+    #[test]
+    fn test_new_with_config() {
+        let mut context = get_context(accounts(0));
+        context.current_account_id(accounts(0));
+        testing_env!(context.build());
+        let contract = CoordinatorContract::new_with_config(
+            accounts(1),
+            Some("We vote for good things.".to_string()),
+            2,
+            5,
+            vec!["hash_a".to_string(), "hash_b".to_string()],
+            Some(accounts(2)),
+        );
+        assert_eq!(contract.get_owner(), accounts(1));
+        assert_eq!(contract.get_manifesto().unwrap().text, "We vote for good things.");
+        assert_eq!(contract.get_worker_bounds(), (2, 5));
+        assert!(contract.is_codehash_approved("hash_a".to_string()));
+        assert!(contract.is_codehash_approved("hash_b".to_string()));
+        assert_eq!(contract.get_registry_contract_id(), Some(accounts(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "max_workers must be >= min_workers")]
+    fn test_new_with_config_invalid_bounds() {
+        let mut context = get_context(accounts(0));
+        context.current_account_id(accounts(0));
+        testing_env!(context.build());
+        CoordinatorContract::new_with_config(accounts(0), None, 3, 2, Vec::new(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected_worker_count must be between 2 and 5")]
+    fn test_start_coordination_outside_worker_bounds() {
+        let mut context = get_context(accounts(0));
+        context.current_account_id(accounts(0));
+        testing_env!(context.build());
+        let mut contract = CoordinatorContract::new_with_config(
+            accounts(0),
+            Some("We vote for good things.".to_string()),
+            2,
+            5,
+            Vec::new(),
+            None,
+        );
+        contract.start_coordination("test".to_string(), 6, 3);
+    }
+
+    /// Coordinator account created by factory bob
+    fn factory_coordinator_id() -> AccountId {
+        "alice-dao.bob".parse().unwrap()
+    }
+
+    #[test]
+    fn test_new_with_config_records_factory() {
+        let mut context = get_context(accounts(1));
+        context.current_account_id(factory_coordinator_id());
+        testing_env!(context.build());
+        let contract =
+            CoordinatorContract::new_with_config(accounts(2), None, 1, 3, Vec::new(), None);
+        assert_eq!(contract.get_factory_account_id(), Some(accounts(1)));
+
+        testing_env!(get_context(accounts(0)).build());
+        let standalone = CoordinatorContract::new(accounts(0));
+        assert_eq!(standalone.get_factory_account_id(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the deploying factory can update code")]
+    fn test_update_code_requires_factory() {
+        let mut context = get_context(accounts(1));
+        context.current_account_id(factory_coordinator_id());
+        testing_env!(context.build());
+        let contract =
+            CoordinatorContract::new_with_config(accounts(2), None, 1, 3, Vec::new(), None);
+
+        // Even the owner cannot push code directly
+        let mut context = get_context(accounts(2));
+        context.current_account_id(factory_coordinator_id());
+        testing_env!(context.build());
+        contract.update_code();
+    }
+
+    #[test]
+    #[should_panic(expected = "Only this account or its parent factory can initialize")]
+    fn test_new_with_config_rejects_other_accounts() {
+        // bob is not the parent of alice
+        let mut context = get_context(accounts(1));
+        context.current_account_id(accounts(0));
+        testing_env!(context.build());
+        CoordinatorContract::new_with_config(accounts(1), None, 1, 3, Vec::new(), None);
+    }
+
+    #[test]
+    fn test_migrate_from_v1_preserves_manifesto() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut approved = IterableSet::new(StorageKey::ApprovedCodehashSet);
+        approved.insert("test_codehash".to_string());
+        approved.flush();
+        let old = CoordinatorContractV1 {
+            owner: accounts(1),
+            approved_codehashes: approved,
+            coordinator_by_account_id: IterableMap::new(StorageKey::CoordinatorByAccountId),
+            current_proposal_id: 7,
+            proposals: IterableMap::new(StorageKey::LegacyProposals),
+            manifesto: Some(build_manifesto("We vote for good things.".to_string())),
+            registered_workers: IterableMap::new(StorageKey::LegacyRegisteredWorkers),
+        };
+        env::state_write(&old);
+
+        let mut migrated = CoordinatorContract::migrate();
+        assert_eq!(migrated.get_owner(), accounts(1));
+        assert_eq!(migrated.get_current_proposal_id(), 7);
+        assert_eq!(migrated.get_manifesto().unwrap().text, "We vote for good things.");
+        assert!(migrated.is_migration_pending());
+        assert!(migrated.migrate_step(None));
+        assert!(migrated.is_codehash_approved("test_codehash".to_string()));
+        assert_eq!(migrated.get_worker_bounds(), (DEFAULT_MIN_WORKERS, DEFAULT_MAX_WORKERS));
+    }
+
+    #[test]
+    fn test_migrate_current_layout_is_identity() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto("We vote for good things.".to_string());
+        env::state_write(&contract);
+
+        let migrated = CoordinatorContract::migrate();
+        assert_eq!(migrated.get_manifesto().unwrap().text, "We vote for good things.");
+    }
+
+    #[test]
+    fn test_migrate_from_v1_copies_records_in_batches() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut approved = IterableSet::new(StorageKey::ApprovedCodehashSet);
+        approved.insert("test_codehash".to_string());
+        approved.flush();
+        let mut workers = IterableMap::new(StorageKey::LegacyRegisteredWorkers);
+        workers.insert(
+            "worker1".to_string(),
+            RegisteredWorkerV1 {
+                worker_id: "worker1".to_string(),
+                account_id: Some(accounts(4)),
+                registered_at: 0,
+                registered_by: accounts(1),
+                active: true,
+            },
+        );
+        workers.flush();
+        let mut proposals = IterableMap::new(StorageKey::LegacyProposals);
+        proposals.insert(
+            3,
+            ProposalV1 {
+                yield_id: CryptoHash::default(),
+                task_config: "test".to_string(),
+                config_hash: hash("test"),
+                timestamp: 0,
+                requester: accounts(1),
+                state: ProposalState::Finalized,
+                expected_worker_count: 2,
+                quorum: 1,
+                worker_submissions: vec![WorkerSubmissionV1 {
+                    worker_id: "worker1".to_string(),
+                    result_hash: "hash1".to_string(),
+                    timestamp: 0,
+                }],
+                finalized_result: Some("result".to_string()),
+            },
+        );
+        proposals.flush();
+        let old = CoordinatorContractV1 {
+            owner: accounts(1),
+            approved_codehashes: approved,
+            coordinator_by_account_id: IterableMap::new(StorageKey::CoordinatorByAccountId),
+            current_proposal_id: 3,
+            proposals,
+            manifesto: None,
+            registered_workers: workers,
+        };
+        env::state_write(&old);
+
+        let mut migrated = CoordinatorContract::migrate();
+        assert_eq!(migrated.get_current_proposal_id(), 3);
+        assert_eq!(migrated.get_factory_account_id(), None);
+        assert!(migrated.get_legacy_task_config());
+
+        // Coordination waits for the old records to be copied in batches
+        assert!(migrated.is_paused());
+        assert!(!migrated.migrate_step(Some(2)));
+        assert!(migrated.get_proposal(3).is_none());
+        assert!(migrated.migrate_step(Some(2)));
+        assert!(!migrated.is_paused());
+        assert!(!migrated.is_migration_pending());
+
+        let approved = migrated.get_approved_codehash("test_codehash".to_string()).unwrap();
+        assert_eq!(approved.role, CodehashRole::Coordinator);
+        assert_eq!(approved.approved_by, accounts(1));
+        assert_eq!(approved.expires_at, None);
+
+        let workers = migrated.get_registered_workers();
+        assert_eq!(workers.len(), 1);
+        assert_eq!(workers[0].account_id, Some(accounts(4)));
+        assert!(workers[0].attestation.is_none());
+        assert_eq!(workers[0].weight, DEFAULT_WORKER_WEIGHT);
+
+        let proposal = migrated.get_proposal(3).unwrap();
+        assert_eq!(proposal.finalized_result, Some("result".to_string()));
+        assert_eq!(proposal.total_weight, 2);
+        assert_eq!(proposal.participating_weight, 1);
+        assert_eq!(proposal.worker_submissions[0].status, SubmissionStatus::Voted);
+        assert_eq!(migrated.get_finalized_coordination(3), Some("result".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "State migration in progress")]
+    fn test_unpause_blocked_during_migration() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut approved = IterableSet::new(StorageKey::ApprovedCodehashSet);
+        approved.insert("test_codehash".to_string());
+        approved.flush();
+        let old = CoordinatorContractV1 {
+            owner: accounts(0),
+            approved_codehashes: approved,
+            coordinator_by_account_id: IterableMap::new(StorageKey::CoordinatorByAccountId),
+            current_proposal_id: 0,
+            proposals: IterableMap::new(StorageKey::LegacyProposals),
+            manifesto: None,
+            registered_workers: IterableMap::new(StorageKey::LegacyRegisteredWorkers),
+        };
+        env::state_write(&old);
+
+        let mut migrated = CoordinatorContract::migrate();
+        migrated.unpause();
+    }
+
+    #[test]
+    fn test_hash_string() {
+        let data = "test data";
+        let result = hash(data);
+        assert_eq!(result.len(), 64);
+    }
+
+    #[test]
+    fn test_proposal_stores_worker_count_and_quorum() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto("We vote for good things.".to_string());
+        // Note: start_coordination calls env::promise_yield_create which is not
+        // available in unit tests, so we verify Proposal construction directly.
+        let proposal = test_proposal(2, 2);
+        assert_eq!(proposal.expected_worker_count, 2);
+        assert_eq!(proposal.quorum, 2);
+    }
+
+    #[test]
+    fn test_record_submissions_wrong_count_panics() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto("We vote for good things.".to_string());
+
+        // Register a coordinator so require_approved_codehash passes
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        contract.register_coordinator(fixture_attestation(ALICE_QUOTE));
+
+        // Manually insert a proposal (bypassing start_coordination which needs yield)
+        let proposal = test_proposal(2, 2);
+        contract.proposals.insert(1, proposal);
+        contract.current_proposal_id = 1;
+
+        // Try to submit 1 worker when 2 are expected — should panic
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.record_worker_submissions(
+                1,
+                vec![WorkerSubmissionInput {
+                    worker_id: "worker1".to_string(),
+                    result_hash: "hash1".to_string(),
+                    status: SubmissionStatus::Voted,
+                }],
+            );
+        }));
+        assert!(result.is_err(), "Should panic when submission count does not match expected_worker_count");
+    }
+
+    #[test]
+    fn test_record_submissions_correct_count_succeeds() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto("We vote for good things.".to_string());
+
+        // Register a coordinator so require_approved_codehash passes
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        contract.register_coordinator(fixture_attestation(ALICE_QUOTE));
+
+        // Manually insert a proposal expecting 2 workers
+        let proposal = test_proposal(2, 2);
+        contract.proposals.insert(1, proposal);
+        contract.current_proposal_id = 1;
+
+        // Submit exactly 2 workers — should succeed
+        contract.record_worker_submissions(
+            1,
+            vec![
+                WorkerSubmissionInput {
+                    worker_id: "worker1".to_string(),
+                    result_hash: "hash1".to_string(),
+                    status: SubmissionStatus::Voted,
+                },
+                WorkerSubmissionInput {
+                    worker_id: "worker2".to_string(),
+                    result_hash: "hash2".to_string(),
+                    status: SubmissionStatus::Voted,
+                },
+            ],
+        );
+
+        let subs = contract.get_worker_submissions(1);
+        assert_eq!(subs.len(), 2);
+        let p = contract.get_proposal(1).unwrap();
+        assert_eq!(p.state, ProposalState::WorkersCompleted);
+    }
+
+    // ========== PAUSE ==========
+
+    #[test]
+    fn test_pause_records_reason_and_time() {
+        let mut context = get_context(accounts(0));
+        context.block_timestamp(42);
+        testing_env!(context.build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.pause("Coordinator key leaked".to_string());
+
+        let info = contract.get_pause_info().unwrap();
+        assert_eq!(info.reason, "Coordinator key leaked");
+        assert_eq!(info.paused_at, 42);
+        assert!(contract.is_paused());
+
+        contract.unpause();
+        assert!(!contract.is_paused());
+        assert!(contract.get_pause_info().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_non_owner_cannot_pause() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        testing_env!(get_context(accounts(1)).build());
+        contract.pause("nope".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused: Coordinator key leaked")]
+    fn test_pause_blocks_start_coordination() {
+        let mut contract = contract_with_proposal();
+        contract.pause("Coordinator key leaked".to_string());
+        contract.start_coordination("test".to_string(), 1, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused: Coordinator key leaked")]
+    fn test_pause_blocks_record_worker_submissions() {
+        let mut contract = contract_with_proposal();
+        contract.pause("Coordinator key leaked".to_string());
+        contract.record_worker_submissions(
+            1,
+            vec![WorkerSubmissionInput {
+                worker_id: "worker1".to_string(),
+                result_hash: "hash1".to_string(),
+                status: SubmissionStatus::Voted,
+            }],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused: Coordinator key leaked")]
+    fn test_pause_blocks_coordinator_resume() {
+        let mut contract = contract_with_proposal();
+        contract.pause("Coordinator key leaked".to_string());
+        contract.coordinator_resume(1, "result".to_string(), hash("test"), hash("result"));
+    }
+
+    #[test]
+    fn test_in_flight_proposal_times_out_while_paused() {
+        let mut contract = contract_with_proposal();
+        contract.pause("Coordinator key leaked".to_string());
+        let _ = contract.return_coordination_result(
+            1,
+            "test".to_string(),
+            Err(PromiseError::Failed),
+        );
+        assert_eq!(contract.get_proposal(1).unwrap().state, ProposalState::TimedOut);
+        assert!(contract.is_paused());
+    }
+
+    // ========== OWNERSHIP ==========
+
+    #[test]
+    fn test_two_step_ownership_transfer() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.propose_owner(accounts(1));
+        assert_eq!(contract.get_pending_owner(), Some(accounts(1)));
+        assert_eq!(contract.get_owner(), accounts(0));
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.accept_ownership();
+        assert_eq!(contract.get_owner(), accounts(1));
+        assert!(contract.get_pending_owner().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the pending owner can accept")]
+    fn test_accept_ownership_wrong_account() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.propose_owner(accounts(1));
+        testing_env!(get_context(accounts(2)).build());
+        contract.accept_ownership();
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the pending owner can accept")]
+    fn test_cancelled_ownership_transfer() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.propose_owner(accounts(1));
+        contract.cancel_ownership_transfer();
+        testing_env!(get_context(accounts(1)).build());
+        contract.accept_ownership();
+    }
+
+    // ========== COUNCIL ==========
+
+    #[test]
+    #[should_panic(expected = "Council governance is enabled")]
+    fn test_council_blocks_direct_owner_action() {
+        let mut contract = owner_contract();
+        add_council(&mut contract);
+        contract.set_manifesto("We vote for good things.".to_string());
+    }
+
+    #[test]
+    fn test_council_action_executes_at_threshold() {
+        let mut contract = owner_contract();
+        add_council(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        let id = contract.propose_council_action(CouncilAction::ApproveCodehash {
+            codehash: "test_codehash".to_string(),
+            label: None,
+            role: None,
+            expires_at: None,
+        });
+        assert!(!contract.is_codehash_approved("test_codehash".to_string()));
+        assert_eq!(contract.get_council_proposal(id).unwrap().confirmations, vec![accounts(1)]);
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.confirm_council_action(id);
+        assert!(contract.is_codehash_approved("test_codehash".to_string()));
+        assert!(contract.get_council_proposal(id).is_none());
+    }
+
+    /// Propose `action` as bob and confirm it as charlie, reaching the 2-of-3 threshold
+    fn pass_council_action(contract: &mut CoordinatorContract, action: CouncilAction) {
+        testing_env!(get_context(accounts(1)).build());
+        let id = contract.propose_council_action(action);
+        testing_env!(get_context(accounts(2)).build());
+        contract.confirm_council_action(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Council governance is enabled")]
+    fn test_council_blocks_owner_unpause() {
+        let mut contract = owner_contract();
+        add_council(&mut contract);
+        pass_council_action(&mut contract, CouncilAction::Pause { reason: "incident".to_string() });
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.unpause();
+    }
+
+    #[test]
+    fn test_council_runs_former_owner_only_actions() {
+        let mut contract = owner_contract();
+        contract.register_worker("worker1".to_string(), None);
+        add_council(&mut contract);
+
+        pass_council_action(&mut contract, CouncilAction::Pause { reason: "incident".to_string() });
+        pass_council_action(&mut contract, CouncilAction::Unpause);
+        assert!(!contract.is_paused());
+
+        pass_council_action(
+            &mut contract,
+            CouncilAction::SetWorkerActive {
+                worker_id: "worker1".to_string(),
+                active: false,
+            },
+        );
+        assert!(!contract.get_registered_workers()[0].active);
+
+        pass_council_action(&mut contract, CouncilAction::ProposeOwner { new_owner: accounts(4) });
+        assert_eq!(contract.get_pending_owner(), Some(accounts(4)));
+
+        pass_council_action(&mut contract, CouncilAction::SetRequireWorkerBinding { required: true });
+        assert!(contract.get_require_worker_binding());
+        pass_council_action(&mut contract, CouncilAction::SetLegacyTaskConfig { enabled: true });
+        assert!(contract.get_legacy_task_config());
+    }
+
+    #[test]
+    #[should_panic(expected = "Council governance is enabled")]
+    fn test_council_blocks_owner_disabling_validation() {
+        let mut contract = owner_contract();
+        add_council(&mut contract);
+        contract.set_legacy_task_config(true);
+    }
+
+    #[test]
+    fn test_council_upgrade_goes_through_factory() {
+        let mut context = get_context(accounts(1));
+        context.current_account_id(factory_coordinator_id());
+        testing_env!(context.build());
+        let mut contract =
+            CoordinatorContract::new_with_config(accounts(0), None, 1, 3, Vec::new(), None);
+        assert_eq!(contract.get_upgrade_authority(), accounts(0));
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.set_council(vec![accounts(1), accounts(2), accounts(3)], 2);
+        assert_eq!(contract.get_upgrade_authority(), env::current_account_id());
+
+        pass_council_action(&mut contract, CouncilAction::UpgradeCode { code_hash: None });
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts.last().unwrap().receiver_id, accounts(1));
+    }
+
+    #[test]
+    fn test_council_pause_and_manifesto() {
+        let mut contract = owner_contract();
+        add_council(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        let pause = contract.propose_council_action(CouncilAction::Pause {
+            reason: "TEE image compromised".to_string(),
+        });
+        let manifesto = contract.propose_council_action(CouncilAction::ProposeManifesto {
+            manifesto_text: "We vote for good things.".to_string(),
+        });
+        testing_env!(get_context(accounts(3)).build());
+        contract.confirm_council_action(pause);
+        contract.confirm_council_action(manifesto);
+        assert_eq!(contract.get_pause_info().unwrap().reason, "TEE image compromised");
+        assert_eq!(
+            contract.get_pending_manifesto().unwrap().manifesto.text,
+            "We vote for good things."
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Already confirmed this council proposal")]
+    fn test_council_double_confirm() {
+        let mut contract = owner_contract();
+        add_council(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        let id = contract.propose_council_action(CouncilAction::RemoveWorker {
+            worker_id: "worker1".to_string(),
+        });
+        contract.confirm_council_action(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only council members can call this function")]
+    fn test_owner_outside_council_cannot_propose() {
+        let mut contract = owner_contract();
+        add_council(&mut contract);
+        contract.propose_council_action(CouncilAction::RemoveWorker {
+            worker_id: "worker1".to_string(),
+        });
+    }
+
+    #[test]
+    fn test_council_can_remove_itself() {
+        let mut contract = owner_contract();
+        add_council(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        let pending = contract.propose_council_action(CouncilAction::RemoveWorker {
+            worker_id: "worker1".to_string(),
+        });
+        let id = contract.propose_council_action(CouncilAction::SetCouncil { council: None });
+        testing_env!(get_context(accounts(2)).build());
+        contract.confirm_council_action(id);
+        assert!(contract.get_council().is_none());
+        assert!(contract.get_council_proposal(pending).is_none());
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.approve_codehash("test_codehash".to_string(), None, None, None);
+        assert!(contract.is_codehash_approved("test_codehash".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Council threshold must be between 1 and the number of members")]
+    fn test_set_council_invalid_threshold() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_council(vec![accounts(1), accounts(2)], 3);
+    }
+
+    // ========== MANIFESTO TIMELOCK ==========
+
+    fn context_at(predecessor: AccountId, timestamp: u64) -> VMContextBuilder {
+        let mut context = get_context(predecessor);
+        context.block_timestamp(timestamp);
+        context
+    }
+
+    #[test]
+    fn test_proposed_manifesto_activates_after_delay() {
+        testing_env!(context_at(accounts(0), 1_000).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto("Old values.".to_string());
+        contract.set_manifesto_delay(MIN_MANIFESTO_DELAY_NS);
+        contract.propose_manifesto("New values.".to_string());
+
+        let pending = contract.get_pending_manifesto().unwrap();
+        assert_eq!(pending.effective_at, 1_000 + MIN_MANIFESTO_DELAY_NS);
+        assert_eq!(contract.get_manifesto().unwrap().text, "Old values.");
+
+        testing_env!(context_at(accounts(0), 999 + MIN_MANIFESTO_DELAY_NS).build());
+        assert_eq!(contract.get_manifesto().unwrap().text, "Old values.");
+
+        testing_env!(context_at(accounts(0), 1_000 + MIN_MANIFESTO_DELAY_NS).build());
+        assert_eq!(contract.get_manifesto().unwrap().text, "New values.");
+        assert!(contract.get_pending_manifesto().is_none());
+    }
+
+    #[test]
+    fn test_due_manifesto_is_activated_on_write() {
+        // start_coordination needs promise_yield_create, so call the activation step directly
+        testing_env!(context_at(accounts(0), 1_000).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto("Old values.".to_string());
+        contract.propose_manifesto("New values.".to_string());
+
+        testing_env!(context_at(accounts(0), 1_000 + DEFAULT_MANIFESTO_DELAY_NS).build());
+        contract.activate_pending_manifesto();
+        assert_eq!(contract.manifesto.as_ref().unwrap().text, "New values.");
+        assert!(contract.pending_manifesto.is_none());
+    }
+
+    #[test]
+    fn test_cancel_manifesto_change() {
+        testing_env!(context_at(accounts(0), 1_000).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto("Old values.".to_string());
+        contract.propose_manifesto("New values.".to_string());
+        contract.cancel_manifesto_change();
+        assert!(contract.get_pending_manifesto().is_none());
+
+        testing_env!(context_at(accounts(0), 1_000 + DEFAULT_MANIFESTO_DELAY_NS).build());
+        assert_eq!(contract.get_manifesto().unwrap().text, "Old values.");
+    }
+
+    #[test]
+    #[should_panic(expected = "No manifesto change queued")]
+    fn test_cancel_after_activation_fails() {
+        testing_env!(context_at(accounts(0), 1_000).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.propose_manifesto("New values.".to_string());
+
+        testing_env!(context_at(accounts(0), 1_000 + DEFAULT_MANIFESTO_DELAY_NS).build());
+        contract.cancel_manifesto_change();
+    }
+
+    #[test]
+    #[should_panic(expected = "Manifesto delay must be at least")]
+    fn test_manifesto_delay_below_minimum_fails() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto_delay(MIN_MANIFESTO_DELAY_NS - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Manifesto delay must be at least")]
+    fn test_council_manifesto_delay_below_minimum_fails() {
+        let mut contract = owner_contract();
+        add_council(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        contract.propose_council_action(CouncilAction::SetManifestoDelay { delay_ns: 0 });
+    }
+
+    #[test]
+    #[should_panic(expected = "Manifesto already set. Use propose_manifesto to change it.")]
+    fn test_set_manifesto_only_once() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.set_manifesto("Old values.".to_string());
+        contract.set_manifesto("New values.".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "A manifesto change is already queued")]
+    fn test_propose_manifesto_while_queued() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.propose_manifesto("First.".to_string());
+        contract.propose_manifesto("Second.".to_string());
+    }
+
+    // ========== CODEHASH METADATA ==========
+
+    #[test]
+    fn test_approve_codehash_records_metadata() {
+        testing_env!(context_at(accounts(0), 1_000).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(
+            "worker_hash".to_string(),
+            Some("worker v1.2".to_string()),
+            Some(CodehashRole::Worker),
+            Some(5_000),
+        );
+        let approved = contract.get_approved_codehash("worker_hash".to_string()).unwrap();
+        assert_eq!(approved.label, "worker v1.2");
+        assert_eq!(approved.role, CodehashRole::Worker);
+        assert_eq!(approved.approved_by, accounts(0));
+        assert_eq!(approved.approved_at, 1_000);
+        assert_eq!(approved.expires_at, Some(5_000));
+        assert!(contract.is_codehash_approved("worker_hash".to_string()));
+
+        testing_env!(context_at(accounts(0), 5_000).build());
+        assert!(!contract.is_codehash_approved("worker_hash".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinator codehash approval has expired")]
+    fn test_expired_codehash_blocks_coordinator() {
+        testing_env!(context_at(accounts(0), 1_000).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, Some(2_000));
+        contract.register_coordinator(fixture_attestation(ALICE_QUOTE));
+
+        testing_env!(context_at(accounts(0), 2_000).build());
+        contract.require_approved_codehash();
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinator codehash is not approved for the Coordinator role")]
+    fn test_codehash_reapproved_as_worker_blocks_coordinator() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        contract.register_coordinator(fixture_attestation(ALICE_QUOTE));
+        contract.approve_codehash(
+            TEST_MEASUREMENT.to_string(),
+            None,
+            Some(CodehashRole::Worker),
+            None,
+        );
+        contract.require_approved_codehash();
+    }
+
+    #[test]
+    #[should_panic(expected = "Codehash not approved")]
+    fn test_register_coordinator_requires_coordinator_role() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(
+            TEST_MEASUREMENT.to_string(),
+            None,
+            Some(CodehashRole::Worker),
+            None,
+        );
+        contract.register_coordinator(fixture_attestation(ALICE_QUOTE));
+    }
+
+    #[test]
+    #[should_panic(expected = "Codehash expiry must be in the future")]
+    fn test_approve_codehash_past_expiry() {
+        testing_env!(context_at(accounts(0), 1_000).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash("test_codehash".to_string(), None, None, Some(1_000));
+    }
+
+    #[test]
+    fn test_list_approved_codehashes_pagination() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        for codehash in ["hash_a", "hash_b", "hash_c"] {
+            contract.approve_codehash(codehash.to_string(), None, None, None);
+        }
+        assert_eq!(contract.list_approved_codehashes(&None, &None).len(), 3);
+        let page = contract.list_approved_codehashes(&Some(1), &Some(1));
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].codehash, "hash_b");
+    }
+
+    // ========== COORDINATOR REGISTRATION ==========
+
+    #[test]
+    fn test_coordinator_self_registration() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.register_coordinator(fixture_attestation(BOB_QUOTE));
+
+        let coordinators = contract.get_coordinators();
+        assert_eq!(coordinators.len(), 1);
+        assert_eq!(coordinators[0].0, accounts(1));
+        let coordinator = contract.get_coordinator(accounts(1)).unwrap();
+        assert_eq!(coordinator.codehash, TEST_MEASUREMENT);
+        assert_eq!(coordinator.checksum, hash_bytes(BOB_QUOTE));
+    }
+
+    #[test]
+    #[should_panic(expected = "Codehash not approved")]
+    fn test_coordinator_registration_requires_approved_codehash() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        testing_env!(get_context(accounts(1)).build());
+        contract.register_coordinator(fixture_attestation(BOB_QUOTE));
+    }
+
+    #[test]
+    fn test_unregister_coordinator() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        testing_env!(get_context(accounts(1)).build());
+        contract.register_coordinator(fixture_attestation(BOB_QUOTE));
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.unregister_coordinator(accounts(1));
+        assert!(contract.get_coordinator(accounts(1)).is_none());
+        assert!(contract.is_coordinator_removed(accounts(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "was unregistered and must be reinstated first")]
+    fn test_unregistered_coordinator_cannot_register_again() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        testing_env!(get_context(accounts(1)).build());
+        contract.register_coordinator(fixture_attestation(BOB_QUOTE));
+        testing_env!(get_context(accounts(0)).build());
+        contract.unregister_coordinator(accounts(1));
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.register_coordinator(fixture_attestation(BOB_QUOTE));
+    }
+
+    #[test]
+    fn test_reinstated_coordinator_can_register_again() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        testing_env!(get_context(accounts(1)).build());
+        contract.register_coordinator(fixture_attestation(BOB_QUOTE));
+        testing_env!(get_context(accounts(0)).build());
+        contract.unregister_coordinator(accounts(1));
+
+        contract.reinstate_coordinator(accounts(1));
+        assert!(!contract.is_coordinator_removed(accounts(1)));
+        testing_env!(get_context(accounts(1)).build());
+        contract.register_coordinator(fixture_attestation(BOB_QUOTE));
+        assert!(contract.get_coordinator(accounts(1)).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinator bob is not barred")]
+    fn test_reinstate_requires_unregistered_coordinator() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.reinstate_coordinator(accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_non_owner_cannot_unregister_coordinator() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        testing_env!(get_context(accounts(1)).build());
+        contract.register_coordinator(fixture_attestation(BOB_QUOTE));
+        contract.unregister_coordinator(accounts(1));
+    }
+
+    #[test]
+    fn test_council_reinstates_coordinator() {
+        let mut contract = owner_contract();
+        contract.removed_coordinators.insert(accounts(1));
+        add_council(&mut contract);
+        pass_council_action(
+            &mut contract,
+            CouncilAction::ReinstateCoordinator {
+                account_id: accounts(1),
+            },
+        );
+        assert!(!contract.is_coordinator_removed(accounts(1)));
+    }
+
+    // ========== ATTESTATION FIXTURES ==========
+
+    fn hash_bytes(quote_hex: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(hex::decode(quote_hex.trim()).unwrap());
+        encode(hasher.finalize())
+    }
+
+    fn decode_fixture(quote_hex: &str) -> Vec<u8> {
+        hex::decode(quote_hex.trim()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_tdx_quote_fixture() {
+        let parsed = parse_tdx_quote(&decode_fixture(ALICE_QUOTE)).unwrap();
+        assert_eq!(parsed.measurement, TEST_MEASUREMENT);
+        assert_eq!(parsed.report_data[..32], Sha256::digest(b"alice")[..]);
+        assert_eq!(parsed.report_data[32..], [0xAB; 32]);
+
+        // Same image, different agent account
+        let bob = parse_tdx_quote(&decode_fixture(BOB_QUOTE)).unwrap();
+        assert_eq!(bob.measurement, TEST_MEASUREMENT);
+        assert_ne!(bob.report_data, parsed.report_data);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_quotes() {
+        let err = parse_tdx_quote(&decode_fixture(TRUNCATED_QUOTE)).err().unwrap();
+        assert!(err.starts_with("Quote too short"));
+        let err = parse_tdx_quote(&decode_fixture(SGX_QUOTE)).err().unwrap();
+        assert_eq!(err, "Unsupported quote: version 3, TEE type 0x0");
+        let err = parse_tdx_quote(&decode_fixture(BAD_SIGNATURE_LEN_QUOTE)).err().unwrap();
+        assert_eq!(err, "Quote signature data is truncated");
+    }
+
+    #[test]
+    fn test_parse_attestation_binds_account() {
+        let checksum =
+            parse_attestation(&fixture_attestation(ALICE_QUOTE), &accounts(0)).unwrap();
+        assert_eq!(checksum, hash_bytes(ALICE_QUOTE));
+
+        let err = parse_attestation(&fixture_attestation(ALICE_QUOTE), &accounts(1))
+            .err()
+            .unwrap();
+        assert_eq!(err, "Quote report_data is not bound to bob");
+    }
+
+    #[test]
+    fn test_parse_attestation_measurement_mismatch() {
+        let mut attestation = fixture_attestation(ALICE_QUOTE);
+        attestation.codehash = "other_codehash".to_string();
+        let err = parse_attestation(&attestation, &accounts(0)).err().unwrap();
+        assert!(err.starts_with("Quote measurement"));
+
+        attestation.quote_hex = "zz".to_string();
+        let err = parse_attestation(&attestation, &accounts(0)).err().unwrap();
+        assert_eq!(err, "Quote is not valid hex");
+    }
+
+    #[test]
+    #[should_panic(expected = "Quote report_data is not bound to bob")]
+    fn test_register_coordinator_with_another_accounts_quote() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        testing_env!(get_context(accounts(1)).build());
+        contract.register_coordinator(fixture_attestation(ALICE_QUOTE));
+    }
+
+    // ========== WORKER ATTESTATION ==========
+
+    fn self_register(contract: &mut CoordinatorContract, worker_id: &str, quote: &str) {
+        contract.register_worker_with_attestation(worker_id.to_string(), fixture_attestation(quote));
+    }
+
+    #[test]
+    fn test_worker_attestation_is_attached() {
+        let mut contract = owner_contract();
+        add_worker_codehash(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        self_register(&mut contract, "worker1", BOB_QUOTE);
+
+        let worker = contract.registered_workers.get("worker1").unwrap();
+        assert_eq!(worker.account_id, Some(accounts(1)));
+        assert_eq!(worker.registered_by, accounts(0));
+        let attestation = worker.attestation.as_ref().unwrap();
+        assert_eq!(attestation.codehash, TEST_MEASUREMENT);
+        assert_eq!(attestation.checksum, hash_bytes(BOB_QUOTE));
+        assert!(contract.is_worker_registered("worker1".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker codehash not approved")]
+    fn test_worker_registration_requires_worker_role() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = CoordinatorContract::new(accounts(0));
+        contract.approve_codehash(TEST_MEASUREMENT.to_string(), None, None, None);
+        testing_env!(get_context(accounts(1)).build());
+        self_register(&mut contract, "worker1", BOB_QUOTE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker worker1 is registered to another account")]
+    fn test_worker_id_cannot_be_taken_over() {
+        let mut contract = owner_contract();
+        add_worker_codehash(&mut contract);
+        self_register(&mut contract, "worker1", ALICE_QUOTE);
+    }
+
+    #[test]
+    fn test_worker_self_registration() {
+        let mut contract = owner_contract();
+        add_worker_codehash(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        self_register(&mut contract, "worker2", BOB_QUOTE);
+
+        let worker = contract.registered_workers.get("worker2").unwrap();
+        assert_eq!(worker.account_id, Some(accounts(1)));
+        assert_eq!(worker.registered_by, accounts(1));
+        assert!(worker.active);
+        assert_eq!(worker.attestation.as_ref().unwrap().checksum, hash_bytes(BOB_QUOTE));
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker worker1 is already registered")]
+    fn test_coordinator_cannot_rebind_registered_worker() {
+        let mut contract = owner_contract();
+        add_worker_codehash(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        self_register(&mut contract, "worker1", BOB_QUOTE);
+
+        // Register charlie as a coordinator directly; attestation is covered above
+        contract.coordinator_by_account_id.insert(
+            accounts(2),
+            Worker {
+                checksum: "checksum".to_string(),
+                codehash: TEST_MEASUREMENT.to_string(),
+            },
+        );
+        testing_env!(get_context(accounts(2)).build());
+        contract.register_worker("worker1".to_string(), Some(accounts(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Council governance is enabled")]
+    fn test_owner_cannot_revive_worker_deactivated_by_council() {
+        let mut contract = owner_contract();
+        add_worker_codehash(&mut contract);
+        add_council(&mut contract);
+        pass_council_action(
+            &mut contract,
+            CouncilAction::SetWorkerActive {
+                worker_id: "worker1".to_string(),
+                active: false,
+            },
+        );
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.register_worker("worker1".to_string(), Some(accounts(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker worker1 is already registered")]
+    fn test_owner_cannot_reregister_deactivated_worker() {
+        let mut contract = owner_contract();
+        add_worker_codehash(&mut contract);
+        contract.deactivate_worker("worker1".to_string());
+        contract.register_worker("worker1".to_string(), Some(accounts(1)));
+    }
+
+    fn bound_submission(worker_id: &str, result_hash: &str) -> Vec<WorkerSubmissionInput> {
+        vec![WorkerSubmissionInput {
+            worker_id: worker_id.to_string(),
+            result_hash: result_hash.to_string(),
+            status: SubmissionStatus::Voted,
+        }]
+    }
+
+    #[test]
+    fn test_bound_submission_is_recorded() {
+        let mut contract = owner_contract();
+        require_binding(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        contract.submit_worker_result(1, "worker1".to_string(), "hash1".to_string());
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.record_worker_submissions(1, bound_submission("worker1", "hash1"));
+        assert_eq!(contract.get_worker_submissions(1).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker worker1 did not post this result from its bound account")]
+    fn test_unposted_submission_rejected() {
+        let mut contract = owner_contract();
+        require_binding(&mut contract);
+        contract.record_worker_submissions(1, bound_submission("worker1", "hash1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker worker1 did not post this result from its bound account")]
+    fn test_submission_must_match_posted_result() {
+        let mut contract = owner_contract();
+        require_binding(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        contract.submit_worker_result(1, "worker1".to_string(), "hash1".to_string());
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.record_worker_submissions(1, bound_submission("worker1", "forged"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the bound account of worker worker1 can post its result")]
+    fn test_only_bound_account_posts_result() {
+        let mut contract = owner_contract();
+        require_binding(&mut contract);
+        contract.submit_worker_result(1, "worker1".to_string(), "hash1".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker worker2 has no bound account")]
+    fn test_unknown_worker_rejected_when_binding_required() {
+        let mut contract = owner_contract();
+        require_binding(&mut contract);
+        contract.record_worker_submissions(1, bound_submission("worker2", "hash1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker worker1 is deactivated")]
+    fn test_deactivated_worker_cannot_reattest() {
+        let mut contract = owner_contract();
+        add_worker_codehash(&mut contract);
+        contract.deactivate_worker("worker1".to_string());
+        testing_env!(get_context(accounts(1)).build());
+        self_register(&mut contract, "worker1", BOB_QUOTE);
+    }
+
+    // ========== WEIGHTED VOTING ==========
+
+    /// Two registered workers with weights 3 and 1 and a proposal snapshotting them
+    fn submission(worker_id: &str) -> WorkerSubmissionInput {
+        WorkerSubmissionInput {
+            worker_id: worker_id.to_string(),
+            result_hash: format!("hash-{}", worker_id),
+            status: SubmissionStatus::Voted,
+        }
+    }
+
+    #[test]
+    fn test_set_worker_weight_updates_total_eligible_weight() {
+        let mut contract = contract_with_proposal();
+        assert_eq!(contract.get_total_eligible_weight(), 4);
+        contract.deactivate_worker("worker1".to_string());
+        assert_eq!(contract.get_total_eligible_weight(), 1);
+
+        // Reactivating keeps the weight
+        contract.activate_worker("worker1".to_string());
+        assert_eq!(contract.get_total_eligible_weight(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker weight must be at least 1")]
+    fn test_zero_worker_weight_rejected() {
+        let mut contract = contract_with_proposal();
+        contract.set_worker_weight("worker1".to_string(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_non_owner_cannot_set_worker_weight() {
+        let mut contract = contract_with_proposal();
+        testing_env!(get_context(accounts(1)).build());
+        contract.set_worker_weight("worker1".to_string(), 5);
+    }
+
+    #[test]
+    fn test_participating_weight_uses_snapshot() {
+        let mut contract = contract_with_proposal();
+        // Changes after creation do not affect the proposal
+        contract.set_worker_weight("worker1".to_string(), 10);
+        contract.record_worker_submissions(1, vec![submission("worker1"), submission("worker2")]);
+
+        assert_eq!(contract.get_proposal_participating_weight(1), Some(4));
+        assert_eq!(contract.get_proposal(1).unwrap().total_weight, 4);
+        assert_eq!(contract.get_proposal_participating_weight(2), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker worker3 is not eligible for proposal #1")]
+    fn test_worker_outside_snapshot_rejected() {
+        let mut contract = contract_with_proposal();
+        contract.register_worker("worker3".to_string(), None);
+        contract.record_worker_submissions(1, vec![submission("worker1"), submission("worker3")]);
+    }
+
+    #[test]
+    fn test_council_sets_worker_weight() {
+        let mut contract = contract_with_proposal();
+        contract.set_council(vec![accounts(0)], 1);
+        contract.propose_council_action(CouncilAction::SetWorkerWeight {
+            worker_id: "worker2".to_string(),
+            weight: 2,
+        });
+        assert_eq!(contract.get_total_eligible_weight(), 5);
+    }
+
+    fn recorded_weighted_proposal() -> Proposal {
+        let mut contract = contract_with_proposal();
+        contract.record_worker_submissions(1, vec![submission("worker1"), submission("worker2")]);
+        contract.get_proposal(1).unwrap()
+    }
+
+    #[test]
+    fn test_weighted_tally_accepted() {
+        let proposal = recorded_weighted_proposal();
+        // worker1 (weight 3) approves, worker2 (weight 1) rejects
+        validate_weighted_tally(&proposal, r#"{"approved":3,"rejected":1,"decision":"Approved"}"#);
+        // Results without a tally are not checked
+        validate_weighted_tally(&proposal, r#"{"aggregatedValue":42}"#);
+        validate_weighted_tally(&proposal, "plain text result");
+    }
+
+    #[test]
+    fn test_agent_non_vote_payload_accepted() {
+        let proposal = recorded_weighted_proposal();
+        // What coordinator-agent's memory-monitor sends for a `{type:"random"}` task
+        let result = concat!(
+            r#"{"aggregatedValue":137,"approved":0,"rejected":0,"decision":"Approved","#,
+            r#""workerCount":2,"timestamp":"2026-10-18T12:00:00.000Z","proposalId":1}"#
+        );
+        validate_weighted_tally(&proposal, result);
+    }
+
+    #[test]
+    #[should_panic(expected = "Decision Approved does not match weighted tally")]
+    fn test_weighted_tally_wrong_decision_rejected() {
+        let proposal = recorded_weighted_proposal();
+        // A strict majority of 4 participating weight needs 3 approvals
+        validate_weighted_tally(&proposal, r#"{"approved":1,"rejected":1,"decision":"Approved"}"#);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tally of 5 exceeds voting weight 4")]
+    fn test_weighted_tally_exceeding_weight_rejected() {
+        let proposal = recorded_weighted_proposal();
+        validate_weighted_tally(&proposal, r#"{"approved":5,"rejected":0,"decision":"Approved"}"#);
+    }
+
+    // ========== MULTI-OPTION PROPOSALS ==========
+
+    const OPTIONS_CONFIG: &str = r#"{"type":"vote","parameters":{"proposal":"Pick a grant",
+        "options":["alpha","beta","gamma"],"resolution":"InstantRunoff"}}"#;
+
+    fn options(resolution: ResolutionMode) -> ProposalOptions {
+        ProposalOptions {
+            options: vec!["alpha".to_string(), "beta".to_string(), "gamma".to_string()],
+            resolution,
+        }
+    }
+
+    #[test]
+    fn test_task_config_options() {
+        let contract = owner_contract();
+        assert!(!contract.get_legacy_task_config());
+        assert_eq!(
+            contract.task_config_options(OPTIONS_CONFIG, 3, 0),
+            Some(options(ResolutionMode::InstantRunoff))
+        );
+        let plurality = r#"{"type":"vote","parameters":{"proposal":"x","options":["yes","no"]}}"#;
+        let plurality = contract.task_config_options(plurality, 3, 0);
+        assert_eq!(plurality.unwrap().resolution, ResolutionMode::Plurality);
+        // Approve/reject proposals
+        let approve_reject = r#"{"type":"vote","parameters":{"proposal":"x"}}"#;
+        assert_eq!(contract.task_config_options(approve_reject, 3, 0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate option alpha")]
+    fn test_task_config_rejects_duplicate_options() {
+        let config = r#"{"type":"vote",
+            "parameters":{"proposal":"x","options":["alpha","beta","alpha"]}}"#;
+        owner_contract().task_config_options(config, 3, 0);
     }
 
     #[test]
-    fn test_initialization() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
-        let contract = CoordinatorContract::new(accounts(0));
-        assert_eq!(contract.get_owner(), accounts(0));
-        assert_eq!(contract.get_current_proposal_id(), 0);
-        assert!(contract.get_manifesto().is_none());
+    #[should_panic(expected = "A proposal needs between 2 and 32 options")]
+    fn test_task_config_rejects_single_option() {
+        let config = r#"{"type":"vote","parameters":{"proposal":"x","options":["alpha"]}}"#;
+        owner_contract().task_config_options(config, 3, 0);
     }
 
     #[test]
-    fn test_set_manifesto() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
-        let mut contract = CoordinatorContract::new(accounts(0));
-        contract.set_manifesto("We vote for good things.".to_string());
-        let manifesto = contract.get_manifesto().unwrap();
-        assert_eq!(manifesto.text, "We vote for good things.");
-        assert_eq!(manifesto.hash.len(), 64);
+    fn test_plurality_resolution() {
+        let plurality = options(ResolutionMode::Plurality);
+        let result = r#"{"tally":{"alpha":1,"beta":3},"winner":"beta"}"#;
+        assert_eq!(resolve_options(&plurality, 4, result), Ok("beta".to_string()));
+        // Ties go to the option declared first
+        let tie = r#"{"tally":{"gamma":2,"beta":2}}"#;
+        assert_eq!(resolve_options(&plurality, 4, tie), Ok("beta".to_string()));
+        assert_eq!(
+            resolve_options(&plurality, 3, result),
+            Err("Tally of 4 exceeds voting weight 3".to_string())
+        );
+        assert_eq!(
+            resolve_options(&plurality, 4, r#"{"tally":{"delta":1}}"#),
+            Err("Unknown option delta".to_string())
+        );
+        assert_eq!(
+            resolve_options(&plurality, 4, r#"{"tally":{"alpha":3,"beta":1},"winner":"beta"}"#),
+            Err("Reported winner beta does not match tally winner alpha".to_string())
+        );
     }
 
     #[test]
-    fn test_approve_codehash() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
-        let mut contract = CoordinatorContract::new(accounts(0));
-        contract.approve_codehash("test_codehash".to_string());
-        assert!(contract.is_codehash_approved("test_codehash".to_string()));
+    fn test_approval_resolution() {
+        let approval = options(ResolutionMode::Approval);
+        // Every voter may approve several options
+        let result = r#"{"tally":{"alpha":3,"beta":4,"gamma":2}}"#;
+        assert_eq!(resolve_options(&approval, 4, result), Ok("beta".to_string()));
+        assert_eq!(
+            resolve_options(&approval, 4, r#"{"tally":{"alpha":5}}"#),
+            Err("Tally of 5 exceeds voting weight 4".to_string())
+        );
+        assert_eq!(
+            resolve_options(&approval, 4, r#"{"tally":{}}"#),
+            Err("No votes for any option".to_string())
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Only owner can call this function")]
-    fn test_non_owner_cannot_approve_codehash() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
-        let mut contract = CoordinatorContract::new(accounts(0));
-        testing_env!(get_context(accounts(1)).build());
-        contract.approve_codehash("test_codehash".to_string());
+    fn test_instant_runoff_resolution() {
+        let irv = options(ResolutionMode::InstantRunoff);
+        // alpha leads on first preferences, but gamma's voters prefer beta
+        let result = r#"{"ballots":[
+            {"ranking":["alpha","beta"],"weight":4},
+            {"ranking":["beta","alpha"],"weight":3},
+            {"ranking":["gamma","beta"],"weight":2}
+        ]}"#;
+        assert_eq!(resolve_options(&irv, 9, result), Ok("beta".to_string()));
+        // A first-round majority wins outright
+        let majority = r#"{"ballots":[
+            {"ranking":["gamma"],"weight":5},
+            {"ranking":["alpha"],"weight":4}
+        ]}"#;
+        assert_eq!(resolve_options(&irv, 9, majority), Ok("gamma".to_string()));
+        assert_eq!(
+            resolve_options(&irv, 9, r#"{"ballots":[{"ranking":["alpha","alpha"],"weight":1}]}"#),
+            Err("Option alpha is ranked twice on a ballot".to_string())
+        );
+        assert_eq!(
+            resolve_options(&irv, 8, result),
+            Err("Tally of 9 exceeds voting weight 8".to_string())
+        );
     }
 
     #[test]
-    fn test_hash_string() {
-        let data = "test data";
-        let result = hash(data);
-        assert_eq!(result.len(), 64);
+    fn test_agent_option_payload_accepted() {
+        // What coordinator-agent's memory-monitor sends for an instant-runoff proposal
+        let result = concat!(
+            r#"{"aggregatedValue":2,"approved":2,"rejected":1,"decision":"Approved","#,
+            r#""workerCount":3,"timestamp":"2026-10-18T12:00:00.000Z","proposalId":1,"#,
+            r#""tally":{},"ballots":[{"ranking":["beta","alpha"],"weight":2},"#,
+            r#"{"ranking":["gamma"],"weight":1}],"winner":"beta"}"#
+        );
+        let irv = options(ResolutionMode::InstantRunoff);
+        assert_eq!(resolve_options(&irv, 3, result), Ok("beta".to_string()));
     }
 
     #[test]
-    fn test_proposal_stores_worker_count_and_quorum() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
-        let mut contract = CoordinatorContract::new(accounts(0));
-        contract.set_manifesto("We vote for good things.".to_string());
-        // Note: start_coordination calls env::promise_yield_create which is not
-        // available in unit tests, so we verify Proposal construction directly.
-        let proposal = Proposal {
-            yield_id: CryptoHash::default(),
-            task_config: "test".to_string(),
-            config_hash: hash("test"),
-            timestamp: 0,
-            requester: accounts(0),
-            state: ProposalState::Created,
-            expected_worker_count: 2,
-            quorum: 2,
-            worker_submissions: Vec::new(),
-            finalized_result: None,
-        };
-        assert_eq!(proposal.expected_worker_count, 2);
-        assert_eq!(proposal.quorum, 2);
+    fn test_finalized_proposal_records_winning_option() {
+        let mut contract = contract_with_proposal();
+        contract.proposals.get_mut(&1).unwrap().options = Some(options(ResolutionMode::Plurality));
+        contract.record_worker_submissions(1, vec![submission("worker1"), submission("worker2")]);
+
+        let result = r#"{"tally":{"alpha":1,"gamma":3}}"#.to_string();
+        contract.return_coordination_result(1, "test".to_string(), Ok(result));
+        let proposal = contract.get_proposal(1).unwrap();
+        assert_eq!(proposal.state, ProposalState::Finalized);
+        assert_eq!(proposal.winning_option, Some("gamma".to_string()));
     }
 
+    // ========== TASK CONFIG ==========
+
     #[test]
-    fn test_record_submissions_wrong_count_panics() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
-        let mut contract = CoordinatorContract::new(accounts(0));
-        contract.set_manifesto("We vote for good things.".to_string());
+    fn test_task_config_accepts_agent_configs() {
+        let contract = owner_contract();
+        let vote = r#"{"type":"vote","parameters":{"proposal":"Fund grants","proposalId":"7",
+            "voting_config":{"min_workers":2,"quorum":2}},"metadata":{"source":"dao-ui"}}"#;
+        assert_eq!(contract.task_config_options(vote, 3, 2), None);
+        let random = r#"{"type":"random","timeout":3000}"#;
+        assert_eq!(contract.task_config_options(random, 1, 0), None);
+    }
 
-        // Register a coordinator so require_approved_codehash passes
-        contract.approve_codehash("test_codehash".to_string());
-        contract.register_coordinator("checksum".to_string(), "test_codehash".to_string());
+    #[test]
+    #[should_panic(expected = "Invalid task config")]
+    fn test_task_config_must_be_typed() {
+        owner_contract().task_config_options("Fund grants", 1, 0);
+    }
 
-        // Manually insert a proposal (bypassing start_coordination which needs yield)
-        let proposal = Proposal {
-            yield_id: CryptoHash::default(),
-            task_config: "test".to_string(),
-            config_hash: hash("test"),
-            timestamp: 0,
-            requester: accounts(0),
-            state: ProposalState::Created,
-            expected_worker_count: 2,
-            quorum: 2,
-            worker_submissions: Vec::new(),
-            finalized_result: None,
-        };
-        contract.proposals.insert(1, proposal);
-        contract.current_proposal_id = 1;
+    #[test]
+    #[should_panic(expected = "Vote task requires parameters.proposal")]
+    fn test_vote_task_requires_proposal() {
+        owner_contract().task_config_options(r#"{"type":"vote","parameters":{}}"#, 1, 0);
+    }
 
-        // Try to submit 1 worker when 2 are expected — should panic
-        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            contract.record_worker_submissions(
-                1,
-                vec![WorkerSubmissionInput {
-                    worker_id: "worker1".to_string(),
-                    result_hash: "hash1".to_string(),
-                }],
-            );
-        }));
-        assert!(result.is_err(), "Should panic when submission count does not match expected_worker_count");
+    #[test]
+    #[should_panic(expected = "Task metadata must be a JSON object")]
+    fn test_task_metadata_must_be_object() {
+        owner_contract().task_config_options(r#"{"type":"random","metadata":"x"}"#, 1, 0);
     }
 
     #[test]
-    fn test_record_submissions_correct_count_succeeds() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
-        let mut contract = CoordinatorContract::new(accounts(0));
-        contract.set_manifesto("We vote for good things.".to_string());
+    #[should_panic(expected = "quorum 1 does not match voting_config.quorum 2")]
+    fn test_task_config_quorum_cross_checked() {
+        let config = r#"{"type":"vote",
+            "parameters":{"proposal":"x","voting_config":{"quorum":2}}}"#;
+        owner_contract().task_config_options(config, 3, 1);
+    }
 
-        // Register a coordinator so require_approved_codehash passes
-        contract.approve_codehash("test_codehash".to_string());
-        contract.register_coordinator("checksum".to_string(), "test_codehash".to_string());
+    #[test]
+    #[should_panic(expected = "expected_worker_count 2 is below voting_config.min_workers 3")]
+    fn test_task_config_min_workers_cross_checked() {
+        let config = r#"{"type":"vote",
+            "parameters":{"proposal":"x","voting_config":{"min_workers":3}}}"#;
+        owner_contract().task_config_options(config, 2, 0);
+    }
 
-        // Manually insert a proposal expecting 2 workers
-        let proposal = Proposal {
-            yield_id: CryptoHash::default(),
-            task_config: "test".to_string(),
-            config_hash: hash("test"),
-            timestamp: 0,
-            requester: accounts(0),
-            state: ProposalState::Created,
-            expected_worker_count: 2,
-            quorum: 2,
-            worker_submissions: Vec::new(),
-            finalized_result: None,
-        };
-        contract.proposals.insert(1, proposal);
-        contract.current_proposal_id = 1;
+    #[test]
+    fn test_legacy_task_config_skips_validation() {
+        let mut contract = owner_contract();
+        contract.set_legacy_task_config(true);
+        assert_eq!(contract.task_config_options("Fund grants", 1, 0), None);
+        assert_eq!(contract.task_config_options(OPTIONS_CONFIG, 3, 0), None);
+    }
 
-        // Submit exactly 2 workers — should succeed
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_non_owner_cannot_set_legacy_task_config() {
+        let mut contract = owner_contract();
+        testing_env!(get_context(accounts(1)).build());
+        contract.set_legacy_task_config(true);
+    }
+
+    // ========== SUBMISSION STATUS ==========
+
+    fn submission_with_status(worker_id: &str, status: SubmissionStatus) -> WorkerSubmissionInput {
+        WorkerSubmissionInput {
+            status,
+            ..submission(worker_id)
+        }
+    }
+
+    /// worker1 (weight 3) abstains, worker2 (weight 1) votes
+    fn proposal_with_abstention() -> Proposal {
+        let mut contract = contract_with_proposal();
         contract.record_worker_submissions(
             1,
             vec![
-                WorkerSubmissionInput {
-                    worker_id: "worker1".to_string(),
-                    result_hash: "hash1".to_string(),
-                },
-                WorkerSubmissionInput {
-                    worker_id: "worker2".to_string(),
-                    result_hash: "hash2".to_string(),
-                },
+                submission_with_status("worker1", SubmissionStatus::Abstained),
+                submission("worker2"),
             ],
         );
+        contract.get_proposal(1).unwrap()
+    }
 
-        let subs = contract.get_worker_submissions(1);
-        assert_eq!(subs.len(), 2);
-        let p = contract.get_proposal(1).unwrap();
-        assert_eq!(p.state, ProposalState::WorkersCompleted);
+    #[test]
+    fn test_abstention_counts_toward_participation_only() {
+        let proposal = proposal_with_abstention();
+        assert_eq!(proposal.participating_weight, 4);
+        assert_eq!(proposal.abstained_weight, 3);
+        assert_eq!(proposal.voting_weight(), 1);
+        // The single voting weight decides
+        validate_weighted_tally(
+            &proposal,
+            r#"{"approved":1,"rejected":0,"abstained":3,"decision":"Approved"}"#,
+        );
+    }
+
+    #[test]
+    fn test_abstention_counts_toward_participation_quorum() {
+        let mut proposal = proposal_with_abstention();
+        proposal.quorum = 4;
+        assert!(proposal.has_participation_quorum());
+    }
+
+    #[test]
+    #[should_panic(expected = "Participation of 1 is below quorum 2")]
+    fn test_coordinator_resume_requires_participation_quorum() {
+        let mut contract = contract_with_proposal();
+        contract.proposals.get_mut(&1).unwrap().quorum = 2;
+        contract.record_worker_submissions(
+            1,
+            vec![
+                submission_with_status("worker1", SubmissionStatus::Errored),
+                submission("worker2"),
+            ],
+        );
+        contract.coordinator_resume(1, "result".to_string(), hash("test"), hash("result"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Tally of 3 exceeds voting weight 1")]
+    fn test_abstention_cannot_be_tallied_as_vote() {
+        let proposal = proposal_with_abstention();
+        validate_weighted_tally(&proposal, r#"{"approved":3,"rejected":0,"decision":"Approved"}"#);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tally reports 0 abstained, recorded 3")]
+    fn test_reported_abstentions_must_match() {
+        let proposal = proposal_with_abstention();
+        validate_weighted_tally(&proposal, r#"{"approved":1,"rejected":0,"abstained":0}"#);
+    }
+
+    #[test]
+    fn test_errored_submission_is_visible() {
+        let mut contract = contract_with_proposal();
+        contract.record_worker_submissions(
+            1,
+            vec![
+                submission_with_status("worker1", SubmissionStatus::Errored),
+                submission("worker2"),
+            ],
+        );
+
+        let submissions = contract.get_worker_submissions(1);
+        assert_eq!(submissions.len(), 2);
+        assert_eq!(submissions[0].status, SubmissionStatus::Errored);
+        assert_eq!(submissions[1].status, SubmissionStatus::Voted);
+        // The errored worker neither participates nor votes
+        assert_eq!(contract.get_proposal_participating_weight(1), Some(1));
+    }
+
+    #[test]
+    fn test_submission_status_defaults_to_voted() {
+        let input: WorkerSubmissionInput =
+            serde_json::from_str(r#"{"worker_id":"worker1","result_hash":"hash1"}"#).unwrap();
+        assert_eq!(input.status, SubmissionStatus::Voted);
+    }
+
+    // ========== CHALLENGES ==========
+
+    #[test]
+    fn test_finalized_result_opens_challenge_period() {
+        let mut contract = contract_with_proposal();
+        settle_proposal(&mut contract);
+        let proposal = contract.get_proposal(1).unwrap();
+        assert_eq!(proposal.state, ProposalState::Finalized);
+        assert_eq!(proposal.challenge_deadline, Some(110));
+        assert_eq!(contract.get_coordinator_bond(accounts(0)), NearToken::from_near(5));
+    }
+
+    #[test]
+    fn test_challenge_disputes_result() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        assert_eq!(contract.get_proposal(1).unwrap().state, ProposalState::Disputed);
+        let challenges = contract.get_challenges(1);
+        assert_eq!(challenges.len(), 1);
+        assert_eq!(challenges[0].challenger, accounts(2));
+        assert_eq!(challenges[0].bond, NearToken::from_near(1));
+        assert_eq!(challenges[0].status, ChallengeStatus::Pending);
+    }
+
+    #[test]
+    #[should_panic(expected = "Challenge bond of 1 NEAR required")]
+    fn test_challenge_requires_bond() {
+        let mut contract = contract_with_proposal();
+        settle_proposal(&mut contract);
+        testing_env!(context_at(accounts(2), 20).build());
+        contract.challenge_result(1, "worker1".to_string(), "Vote misreported".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Challenge period for proposal #1 has ended")]
+    fn test_challenge_after_period_rejected() {
+        let mut contract = contract_with_proposal();
+        settle_proposal(&mut contract);
+        let mut context = context_at(accounts(2), 111);
+        testing_env!(context.attached_deposit(NearToken::from_near(1)).build());
+        contract.challenge_result(1, "worker1".to_string(), "Vote misreported".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker worker3 has no submission for proposal #1")]
+    fn test_challenge_must_reference_submission() {
+        let mut contract = contract_with_proposal();
+        settle_proposal(&mut contract);
+        testing_env!(context_at(accounts(2), 20).attached_deposit(NearToken::from_near(1)).build());
+        contract.challenge_result(1, "worker3".to_string(), "Vote misreported".to_string());
+    }
+
+    #[test]
+    fn test_upheld_challenge_overturns_and_slashes() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        testing_env!(context_at(accounts(0), 30).build());
+        contract.resolve_challenge(0, true);
+
+        assert_eq!(contract.get_proposal(1).unwrap().state, ProposalState::Overturned);
+        assert_eq!(contract.get_challenge(0).unwrap().status, ChallengeStatus::Upheld);
+        assert_eq!(contract.get_coordinator_bond(accounts(0)), NearToken::from_near(0));
+        assert_eq!(contract.get_finalized_coordination(1), None);
+
+        // Challenger gets their bond back plus the slashed coordinator bond
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(2));
+        assert!(matches!(
+            receipts[0].actions[0],
+            near_sdk::mock::MockAction::Transfer { deposit, .. }
+                if deposit == NearToken::from_near(6)
+        ));
+    }
+
+    #[test]
+    fn test_dismissed_challenge_restores_result() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        testing_env!(context_at(accounts(0), 30).build());
+        contract.resolve_challenge(0, false);
+
+        assert_eq!(contract.get_proposal(1).unwrap().state, ProposalState::Finalized);
+        assert_eq!(contract.get_challenge(0).unwrap().status, ChallengeStatus::Dismissed);
+        assert_eq!(contract.get_coordinator_bond(accounts(0)), NearToken::from_near(5));
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, accounts(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Challenge #0 is already resolved")]
+    fn test_challenge_resolved_once() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        testing_env!(context_at(accounts(0), 30).build());
+        contract.resolve_challenge(0, false);
+        contract.resolve_challenge(0, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_non_owner_cannot_resolve_challenge() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        contract.resolve_challenge(0, true);
+    }
+
+    #[test]
+    fn test_council_resolves_challenge() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        testing_env!(context_at(accounts(0), 30).build());
+        contract.set_council(vec![accounts(0)], 1);
+        contract.propose_council_action(CouncilAction::ResolveChallenge {
+            challenge_id: 0,
+            upheld: true,
+        });
+        assert_eq!(contract.get_proposal(1).unwrap().state, ProposalState::Overturned);
+    }
+
+    #[test]
+    fn test_clear_proposal_waits_for_challenges() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        testing_env!(context_at(accounts(0), 30).build());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.clear_proposal(1);
+        }));
+        assert!(result.is_err(), "Disputed proposal should not be cleared");
+
+        contract.resolve_challenge(0, false);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.clear_proposal(1);
+        }));
+        assert!(result.is_err(), "Proposal should not be cleared inside its challenge period");
+
+        testing_env!(context_at(accounts(0), 111).build());
+        contract.clear_proposal(1);
+        assert!(contract.get_proposal(1).is_none());
+    }
+
+    #[test]
+    fn test_coordinator_bond_locked_during_challenge_period() {
+        let mut contract = contract_with_proposal();
+        settle_proposal(&mut contract);
+        testing_env!(context_at(accounts(0), 50).build());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.withdraw_coordinator_bond(NearToken::from_near(1));
+        }));
+        assert!(result.is_err(), "Bond should be locked until the challenge period ends");
+
+        testing_env!(context_at(accounts(0), 111).build());
+        contract.withdraw_coordinator_bond(NearToken::from_near(2));
+        assert_eq!(contract.get_coordinator_bond(accounts(0)), NearToken::from_near(3));
+    }
+
+    #[test]
+    fn test_bond_lock_tracks_open_disputes() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        let lock = contract.get_coordinator_bond_lock(accounts(0));
+        assert_eq!(lock, BondLock { locked_until: 110, open_disputes: 1 });
+
+        // Still locked after the period while the challenge is pending
+        testing_env!(context_at(accounts(0), 111).build());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.withdraw_coordinator_bond(NearToken::from_near(1));
+        }));
+        assert!(result.is_err(), "Bond should stay locked while a challenge is pending");
+
+        testing_env!(context_at(accounts(0), 111).build());
+        contract.resolve_challenge(0, false);
+        assert_eq!(contract.get_coordinator_bond_lock(accounts(0)).open_disputes, 0);
+        contract.withdraw_coordinator_bond(NearToken::from_near(5));
+        assert_eq!(contract.get_coordinator_bond(accounts(0)), NearToken::from_near(0));
+    }
+
+    #[test]
+    fn test_upheld_challenge_slash_is_bounded() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        testing_env!(context_at(accounts(0), 25).attached_deposit(NearToken::from_near(3)).build());
+        contract.deposit_coordinator_bond();
+        testing_env!(context_at(accounts(0), 30).build());
+        contract.resolve_challenge(0, true);
+
+        // Only the minimum bond is slashed, the rest stays with the coordinator
+        assert_eq!(contract.get_coordinator_bond(accounts(0)), NearToken::from_near(3));
+        assert_eq!(contract.get_coordinator_bond_lock(accounts(0)).open_disputes, 0);
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert!(matches!(
+            receipts[0].actions[0],
+            near_sdk::mock::MockAction::Transfer { deposit, .. }
+                if deposit == NearToken::from_near(6)
+        ));
+    }
+
+    #[test]
+    fn test_dismissed_challenge_bond_goes_to_coordinator() {
+        let mut contract = contract_with_proposal();
+        open_challenge(&mut contract);
+        contract.proposals.get_mut(&1).unwrap().resolved_by = Some(accounts(4));
+        testing_env!(context_at(accounts(0), 30).build());
+        contract.resolve_challenge(0, false);
+
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, accounts(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinator bond below the required 10 NEAR")]
+    fn test_coordinator_resume_requires_bond() {
+        let mut contract = contract_with_proposal();
+        settle_proposal(&mut contract);
+        contract.set_challenge_config(ChallengeConfig {
+            period_ns: 100,
+            challenge_bond: NearToken::from_near(1),
+            min_coordinator_bond: NearToken::from_near(10),
+        });
+        contract.coordinator_resume(1, "result".to_string(), hash("test"), hash("result"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only registered coordinators can post a bond")]
+    fn test_unregistered_account_cannot_post_bond() {
+        let mut contract = contract_with_proposal();
+        settle_proposal(&mut contract);
+        testing_env!(context_at(accounts(3), 20).attached_deposit(NearToken::from_near(1)).build());
+        contract.deposit_coordinator_bond();
     }
 }
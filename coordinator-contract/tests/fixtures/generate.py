@@ -0,0 +1,67 @@
+#!/usr/bin/env python3
+"""Regenerate the synthetic TDX quote fixtures used by the unit tests.
+
+Quotes follow the Intel TDX DCAP v4 layout (48-byte header, 584-byte TD
+report body, u32 signature data length, signature data). The signature data
+is filler: the contract does not verify DCAP signatures or collateral.
+"""
+import hashlib
+import os
+import struct
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+
+MRTD = bytes([0x11]) * 48
+RTMRS = [bytes([0x20 + i]) * 48 for i in range(4)]
+AGENT_KEY_HASH = bytes([0xAB]) * 32
+
+
+def header(version=4, tee_type=0x81):
+    # version, attestation key type (ECDSA-P256), TEE type, QE SVN, PCE SVN,
+    # QE vendor ID, user data
+    return struct.pack("<HHIHH", version, 2, tee_type, 0, 0) + bytes(16) + bytes(20)
+
+
+def td_report(account_id):
+    report_data = hashlib.sha256(account_id.encode()).digest() + AGENT_KEY_HASH
+    body = (
+        bytes(16)  # TEE TCB SVN
+        + bytes(48)  # MRSEAM
+        + bytes(48)  # MRSIGNERSEAM
+        + bytes(8)  # SEAM attributes
+        + bytes(8)  # TD attributes
+        + bytes(8)  # XFAM
+        + MRTD
+        + bytes(48)  # MRCONFIGID
+        + bytes(48)  # MROWNER
+        + bytes(48)  # MROWNERCONFIG
+        + b"".join(RTMRS)
+        + report_data
+    )
+    assert len(body) == 584
+    return body
+
+
+def quote(account_id, version=4, tee_type=0x81, signature_len=64, signature=None):
+    signature = bytes(64) if signature is None else signature
+    return (
+        header(version, tee_type)
+        + td_report(account_id)
+        + struct.pack("<I", signature_len)
+        + signature
+    )
+
+
+def write(name, data):
+    with open(os.path.join(HERE, name), "w") as f:
+        f.write(data.hex() + "\n")
+
+
+write("tdx_v4_alice.hex", quote("alice"))
+write("tdx_v4_bob.hex", quote("bob"))
+write("tdx_v4_truncated.hex", quote("alice")[:300])
+write("tdx_v4_bad_signature_len.hex", quote("alice", signature_len=4096))
+write("sgx_v3.hex", quote("alice", version=3, tee_type=0x00))
+
+measurement = hashlib.sha256(MRTD + b"".join(RTMRS)).hexdigest()
+print("measurement:", measurement)
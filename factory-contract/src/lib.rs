@@ -3,102 +3,1362 @@ Coordinator Factory Contract
 ============================
 Deploys sovereign coordinator contract instances via the NEAR factory pattern.
 Each new coordinator gets their own sub-account with the coordinator contract
-WASM deployed and initialized with the calling wallet as owner.
+WASM deployed and initialized (via `new_with_config`) with the calling wallet
+as owner, plus the initial manifesto, worker bounds, approved codehashes and
+registry contract passed to `create_coordinator`.
 
 Sub-account naming: `{prefix}.{factory_account_id}`
 e.g. calling create_coordinator("alice-dao", ...) on coord-factory.agents-coordinator.testnet
      creates alice-dao.coord-factory.agents-coordinator.testnet
 
-The coordinator contract WASM is embedded at compile time from the coordinator-contract build.
-To update the embedded WASM, rebuild coordinator-contract first, then rebuild this factory.
+Coordinator contract WASM versions are stored in state, keyed by their SHA-256 hash.
+The owner uploads a new build with `upload_code` (raw WASM bytes as the call input)
+and picks the version used by default with `set_default_version`; no factory
+redeploy is needed to ship a new coordinator version.
+
+The `prefix` must be a single account segment (no dots) that is not on the
+owner-managed reserved-name list and not already used by a tracked coordinator;
+`is_prefix_available` checks this ahead of time.
+
+Creating a coordinator costs the storage of its WASM plus the owner-set
+`min_deposit` (account balance and coordinator state), plus an optional
+`creation_fee` that is sent to the treasury once the coordinator is created.
+The owner can `pause` creation.
+
+Every created coordinator is tracked in `coordinators`. If the create/deploy/init
+batch fails, `on_coordinator_created` drops the entry and refunds the caller.
+
+When a `registry_enrollment` is passed, the coordinator is also listed in its
+registry (the `registry_contract_id` argument, or the factory's configured one)
+via the registry's `register_coordinator_for`
+(with the caller as the record's account), forwarding `registry_enrollment.deposit`.
+The registry admin must add this factory as a trusted factory.
+
+Upgrades are opt-in: a coordinator's upgrade authority calls `upgrade_coordinator`,
+the factory confirms it with the coordinator (`get_upgrade_authority`) and pushes
+the stored WASM to its `update_code` method, which redeploys itself and runs
+`migrate`. The authority is the owner, or the coordinator itself while it has a
+council, so council-governed coordinators upgrade through a council action.
 */
 
-use near_sdk::{env, near, AccountId, Gas, NearToken, PanicOnDefault, Promise};
+use near_sdk::{
+    env, near,
+    store::{IterableMap, IterableSet, LookupMap},
+    AccountId, BorshStorageKey, Gas, NearToken, PanicOnDefault, Promise, PromiseError,
+    PromiseOrValue, PromiseResult,
+};
 use serde_json::json;
 use std::str::FromStr;
 
-// Coordinator contract WASM embedded at compile time.
-// Rebuild coordinator-contract before rebuilding factory to pick up changes.
-const COORDINATOR_WASM: &[u8] =
-    include_bytes!("../../coordinator-contract/target/near/coordinator_contract.wasm");
+/// Gas for calling `new_with_config` on the newly deployed coordinator contract.
+const INIT_GAS: Gas = Gas::from_tgas(20);
+
+/// Gas for the `on_coordinator_created` callback.
+const CALLBACK_GAS: Gas = Gas::from_tgas(10);
+
+/// Gas for `register_coordinator_for` on the registry.
+const REGISTER_GAS: Gas = Gas::from_tgas(20);
+
+/// Gas for `on_coordinator_created` when it also enrolls in the registry.
+const ENROLL_CALLBACK_GAS: Gas = Gas::from_tgas(40);
+
+/// Gas for querying `get_upgrade_authority` on a coordinator before upgrading it.
+const GET_AUTHORITY_GAS: Gas = Gas::from_tgas(5);
 
-/// Gas for calling `new` on the newly deployed coordinator contract.
-const INIT_GAS: Gas = Gas::from_tgas(10);
+/// Gas for `update_code` on a coordinator (self-deploy + `migrate`).
+const UPDATE_CODE_GAS: Gas = Gas::from_tgas(100);
 
-/// Minimum deposit required to create a coordinator account.
-/// Covers: new account balance (1 NEAR) + coordinator contract storage (~1.5 NEAR) + buffer.
-const MIN_DEPOSIT: NearToken = NearToken::from_near(3);
+/// Gas for the `on_upgrade_authority_checked` callback, which forwards `UPDATE_CODE_GAS`.
+const UPGRADE_CALLBACK_GAS: Gas = Gas::from_tgas(130);
+
+/// Mirrors the coordinator contract's manifesto size limit so bad input
+/// fails here instead of inside the deploy batch.
+const MAX_MANIFESTO_LEN: usize = 10000;
+
+/// Prefixes reserved at init so coordinators can't impersonate infrastructure accounts.
+const DEFAULT_RESERVED_NAMES: [&str; 6] =
+    ["admin", "owner", "factory", "registry", "coordinator", "root"];
+
+/// Default deposit on top of the WASM storage cost for a new coordinator account.
+/// Covers: new account balance + coordinator contract state + buffer.
+const DEFAULT_MIN_DEPOSIT: NearToken = NearToken::from_near(1);
+
+#[derive(BorshStorageKey)]
+#[near]
+pub enum StorageKey {
+    Coordinators,
+    CodeVersions,
+    CodeBlobs,
+    ReservedNames,
+}
+
+/// Metadata of a stored coordinator WASM version
+#[near(serializers = [json, borsh])]
+#[derive(Clone)]
+pub struct CodeVersion {
+    /// SHA-256 of the WASM (hex)
+    pub hash: String,
+    pub size: u64,
+    pub uploaded_at: u64,
+}
+
+/// Optional settings forwarded to a new coordinator's `new_with_config`
+#[near(serializers = [json])]
+#[derive(Clone, Default)]
+pub struct CoordinatorSettings {
+    pub manifesto_text: Option<String>,
+    pub approved_codehashes: Option<Vec<String>>,
+    /// Defaults to the factory's configured registry
+    pub registry_contract_id: Option<AccountId>,
+}
+
+/// Registry listing to create alongside a new coordinator
+#[near(serializers = [json])]
+#[derive(Clone)]
+pub struct RegistryEnrollment {
+    pub coordinator_did: String,
+    pub endpoint_url: String,
+    pub cvm_id: String,
+    /// Registry-approved TEE codehash of the coordinator agent
+    pub codehash: String,
+    /// Registry deposit, taken out of the attached deposit
+    pub deposit: NearToken,
+}
+
+/// Callback args carrying a coordinator through its create/deploy/init batch
+#[near(serializers = [json])]
+pub struct PendingCoordinator {
+    pub account_id: AccountId,
+    pub owner: AccountId,
+    /// Full attached deposit, refunded if the batch fails
+    pub deposit: NearToken,
+    pub creation_fee: NearToken,
+    pub min_workers: u8,
+    pub max_workers: u8,
+    /// Registry the coordinator was configured with, where enrollment goes
+    pub registry_contract_id: Option<AccountId>,
+    pub registry_enrollment: Option<RegistryEnrollment>,
+}
+
+/// A coordinator contract instance deployed by this factory
+#[near(serializers = [json, borsh])]
+#[derive(Clone)]
+pub struct CoordinatorInstance {
+    pub account_id: AccountId,
+    pub owner: AccountId,
+    /// SHA-256 of the coordinator WASM deployed to the instance
+    pub wasm_version: String,
+    pub created_at: u64,
+}
 
 #[near(contract_state)]
 #[derive(PanicOnDefault)]
-pub struct CoordinatorFactory {}
+pub struct CoordinatorFactory {
+    pub owner: AccountId,
+    pub coordinators: IterableMap<AccountId, CoordinatorInstance>,
+    /// Metadata of stored coordinator WASM versions, keyed by SHA-256 hex
+    pub code_versions: IterableMap<String, CodeVersion>,
+    /// Stored coordinator WASM blobs, keyed by SHA-256 hex
+    pub code_blobs: LookupMap<String, Vec<u8>>,
+    /// Version deployed when `create_coordinator` is called without `code_hash`
+    pub default_version: Option<String>,
+    /// Registry that new coordinators are enrolled in and initialized with
+    pub registry_contract_id: Option<AccountId>,
+    /// Prefixes that can't be used for new coordinators
+    pub reserved_names: IterableSet<String>,
+    /// Deposit required on top of the WASM storage cost
+    pub min_deposit: NearToken,
+    /// Fee charged per created coordinator, sent to `treasury`
+    pub creation_fee: NearToken,
+    pub treasury: Option<AccountId>,
+    /// Blocks `create_coordinator` while set
+    pub paused: bool,
+}
 
 #[near]
 impl CoordinatorFactory {
     #[init]
-    pub fn new() -> Self {
-        Self {}
+    #[private]
+    pub fn new(owner: AccountId) -> Self {
+        let mut reserved_names = IterableSet::new(StorageKey::ReservedNames);
+        for name in DEFAULT_RESERVED_NAMES {
+            reserved_names.insert(name.to_string());
+        }
+        Self {
+            owner,
+            coordinators: IterableMap::new(StorageKey::Coordinators),
+            code_versions: IterableMap::new(StorageKey::CodeVersions),
+            code_blobs: LookupMap::new(StorageKey::CodeBlobs),
+            default_version: None,
+            registry_contract_id: None,
+            reserved_names,
+            min_deposit: DEFAULT_MIN_DEPOSIT,
+            creation_fee: NearToken::from_yoctonear(0),
+            treasury: None,
+            paused: false,
+        }
+    }
+
+    /// Re-initialize over the previous factory layout.
+    /// Coordinators deployed before this upgrade are not tracked and
+    /// coordinator code must be uploaded again with `upload_code`.
+    #[init(ignore_state)]
+    #[private]
+    pub fn force_reinitialize(owner: AccountId) -> Self {
+        Self::new(owner)
+    }
+
+    // ========== CODE VERSIONS ==========
+
+    /// Store a coordinator WASM version (owner only).
+    /// The raw WASM bytes are passed as the call input, not as JSON.
+    /// The first uploaded version becomes the default.
+    ///
+    /// Returns the SHA-256 hash (hex) identifying the version.
+    pub fn upload_code(&mut self) -> String {
+        self.assert_owner();
+        let code = env::input().unwrap_or_else(|| env::panic_str("No code provided"));
+        assert!(!code.is_empty(), "No code provided");
+        let hash = hex_encode(&env::sha256(&code));
+
+        if !self.code_versions.contains_key(&hash) {
+            self.code_versions.insert(
+                hash.clone(),
+                CodeVersion {
+                    hash: hash.clone(),
+                    size: code.len() as u64,
+                    uploaded_at: env::block_timestamp(),
+                },
+            );
+            self.code_blobs.insert(hash.clone(), code);
+        }
+        if self.default_version.is_none() {
+            self.default_version = Some(hash.clone());
+        }
+        env::log_str(&format!("Uploaded coordinator code: {}", hash));
+        hash
     }
 
+    /// Set the version deployed by default (owner only).
+    pub fn set_default_version(&mut self, code_hash: String) {
+        self.assert_owner();
+        assert!(
+            self.code_versions.contains_key(&code_hash),
+            "Unknown code version: {}",
+            code_hash
+        );
+        self.default_version = Some(code_hash.clone());
+        env::log_str(&format!("Default coordinator code set to {}", code_hash));
+    }
+
+    /// Delete a stored version (owner only). The default version cannot be removed.
+    pub fn remove_code(&mut self, code_hash: String) {
+        self.assert_owner();
+        assert!(
+            self.default_version.as_ref() != Some(&code_hash),
+            "Cannot remove the default code version"
+        );
+        assert!(
+            self.code_versions.remove(&code_hash).is_some(),
+            "Unknown code version: {}",
+            code_hash
+        );
+        self.code_blobs.remove(&code_hash);
+        env::log_str(&format!("Removed coordinator code: {}", code_hash));
+    }
+
+    /// Set the registry new coordinators are enrolled in (owner only).
+    pub fn set_registry_contract_id(&mut self, registry_contract_id: Option<AccountId>) {
+        self.assert_owner();
+        self.registry_contract_id = registry_contract_id;
+        env::log_str(&format!(
+            "Registry contract set to {:?}",
+            self.registry_contract_id
+        ));
+    }
+
+    // ========== CONFIG ==========
+
+    /// Set the deposit required on top of the WASM storage cost (owner only).
+    pub fn set_min_deposit(&mut self, min_deposit: NearToken) {
+        self.assert_owner();
+        self.min_deposit = min_deposit;
+        env::log_str(&format!("Min deposit set to {}", min_deposit));
+    }
+
+    /// Set the per-coordinator creation fee and the treasury it is sent to (owner only).
+    pub fn set_creation_fee(&mut self, creation_fee: NearToken, treasury: Option<AccountId>) {
+        self.assert_owner();
+        assert!(
+            creation_fee.is_zero() || treasury.is_some(),
+            "A treasury is required for a non-zero creation fee"
+        );
+        self.creation_fee = creation_fee;
+        self.treasury = treasury;
+        env::log_str(&format!(
+            "Creation fee set to {} (treasury {:?})",
+            self.creation_fee, self.treasury
+        ));
+    }
+
+    /// Block coordinator creation (owner only).
+    pub fn pause(&mut self) {
+        self.assert_owner();
+        self.paused = true;
+        env::log_str("Factory paused");
+    }
+
+    /// Resume coordinator creation (owner only).
+    pub fn unpause(&mut self) {
+        self.assert_owner();
+        self.paused = false;
+        env::log_str("Factory unpaused");
+    }
+
+    // ========== RESERVED NAMES ==========
+
+    /// Reserve a prefix so it can't be used for new coordinators (owner only).
+    pub fn add_reserved_name(&mut self, name: String) {
+        self.assert_owner();
+        self.reserved_names.insert(name.clone());
+        env::log_str(&format!("Reserved name: {}", name));
+    }
+
+    /// Release a reserved prefix (owner only).
+    pub fn remove_reserved_name(&mut self, name: String) {
+        self.assert_owner();
+        assert!(self.reserved_names.remove(&name), "Name {} is not reserved", name);
+        env::log_str(&format!("Released reserved name: {}", name));
+    }
+
+    // ========== COORDINATORS ==========
+
     /// Deploy a new coordinator contract instance.
     ///
     /// - Creates sub-account `{prefix}.{current_account_id}`
-    /// - Deploys coordinator contract WASM to it
-    /// - Calls `new(owner: caller)` to initialize with caller as owner
-    /// - Attached deposit funds the new account (minimum 3 NEAR)
+    /// - Deploys the coordinator WASM version `code_hash` (or the default version) to it
+    /// - Calls `new_with_config` with caller as owner, the worker bounds and `settings`
+    /// - Attached deposit funds the new account (WASM storage + `min_deposit`) plus
+    ///   the creation fee and the registry deposit when `registry_enrollment` is given
+    /// - Records the instance; on failure the entry is dropped and the deposit refunded
+    /// - Optionally lists the coordinator in its registry, owned by the caller
+    ///
+    /// `settings.registry_contract_id` defaults to the factory's configured registry.
     ///
-    /// Returns a Promise that resolves to the new coordinator's AccountId.
+    /// Returns a Promise that resolves to the new coordinator's AccountId,
+    /// or `null` if deployment failed and the deposit was refunded.
     #[payable]
     pub fn create_coordinator(
         &mut self,
         prefix: AccountId,      // e.g. "alice-dao" → alice-dao.coord-factory.agents-coordinator.testnet
         min_workers: u8,
         max_workers: u8,
+        settings: Option<CoordinatorSettings>,
+        code_hash: Option<String>,
+        registry_enrollment: Option<RegistryEnrollment>,
     ) -> Promise {
-        // Validate prefix: must be a valid sub-account prefix
+        assert!(!self.paused, "Factory is paused");
+
+        // Validate prefix: must be a single, unreserved, unused sub-account segment
         let factory_id = env::current_account_id();
-        let new_account_id = AccountId::from_str(
-            &format!("{}.{}", prefix, factory_id)
-        ).unwrap_or_else(|_| env::panic_str("Invalid prefix: cannot form valid account ID"));
+        let new_account_id = self
+            .check_prefix(prefix.as_str())
+            .unwrap_or_else(|e| env::panic_str(&e));
 
-        // Enforce minimum deposit
-        let deposit = env::attached_deposit();
-        assert!(
-            deposit >= MIN_DEPOSIT,
-            "Minimum deposit is {} NEAR, got {} NEAR",
-            MIN_DEPOSIT.as_near(),
-            deposit.as_near()
-        );
+        let CoordinatorSettings {
+            manifesto_text,
+            approved_codehashes,
+            registry_contract_id,
+        } = settings.unwrap_or_default();
+
+        let registry_contract_id =
+            registry_contract_id.or_else(|| self.registry_contract_id.clone());
+        if registry_enrollment.is_some() {
+            assert!(
+                registry_contract_id.is_some(),
+                "No registry configured for enrollment"
+            );
+        }
 
         // Validate min/max workers
         assert!(min_workers >= 1, "min_workers must be >= 1");
         assert!(max_workers >= min_workers, "max_workers must be >= min_workers");
 
+        if let Some(text) = &manifesto_text {
+            assert!(
+                text.len() <= MAX_MANIFESTO_LEN,
+                "Manifesto text needs to be under 10,000 characters"
+            );
+        }
+
+        let wasm_version = code_hash
+            .or_else(|| self.default_version.clone())
+            .unwrap_or_else(|| env::panic_str("No coordinator code uploaded"));
+        let code = self
+            .code_blobs
+            .get(&wasm_version)
+            .unwrap_or_else(|| {
+                env::panic_str(&format!("Unknown code version: {}", wasm_version))
+            })
+            .clone();
+
+        // Enforce the deposit (excluding the creation fee and registry deposit)
+        let deposit = env::attached_deposit();
+        let registry_deposit = registry_enrollment
+            .as_ref()
+            .map_or(NearToken::from_near(0), |e| e.deposit);
+        let account_deposit = deposit
+            .saturating_sub(self.creation_fee)
+            .saturating_sub(registry_deposit);
+        let required = self.account_deposit_for(code.len() as u64);
+        assert!(
+            account_deposit >= required,
+            "Minimum deposit is {}, got {}",
+            required.saturating_add(self.creation_fee).saturating_add(registry_deposit),
+            deposit
+        );
+
         let owner = env::predecessor_account_id();
 
-        // Build the init args for coordinator-contract's `new(owner: AccountId)`
-        let init_args = json!({ "owner": owner }).to_string().into_bytes();
+        // Build the init args for coordinator-contract's `new_with_config`
+        let init_args = json!({
+            "owner": owner,
+            "manifesto_text": manifesto_text,
+            "min_workers": min_workers,
+            "max_workers": max_workers,
+            "approved_codehashes": approved_codehashes.unwrap_or_default(),
+            "registry_contract_id": &registry_contract_id,
+        })
+        .to_string()
+        .into_bytes();
 
-        Promise::new(new_account_id)
+        self.coordinators.insert(
+            new_account_id.clone(),
+            CoordinatorInstance {
+                account_id: new_account_id.clone(),
+                owner: owner.clone(),
+                wasm_version,
+                created_at: env::block_timestamp(),
+            },
+        );
+
+        let callback_gas = if registry_enrollment.is_some() {
+            ENROLL_CALLBACK_GAS
+        } else {
+            CALLBACK_GAS
+        };
+
+        Promise::new(new_account_id.clone())
             .create_account()
-            .transfer(deposit)
-            .deploy_contract(COORDINATOR_WASM.to_vec())
-            .function_call("new".to_string(), init_args, NearToken::from_yoctonear(0), INIT_GAS)
+            .transfer(account_deposit)
+            .deploy_contract(code)
+            .function_call(
+                "new_with_config".to_string(),
+                init_args,
+                NearToken::from_yoctonear(0),
+                INIT_GAS,
+            )
+            .then(
+                Self::ext(factory_id)
+                    .with_static_gas(callback_gas)
+                    .on_coordinator_created(PendingCoordinator {
+                        account_id: new_account_id,
+                        owner,
+                        deposit,
+                        creation_fee: self.creation_fee,
+                        min_workers,
+                        max_workers,
+                        registry_contract_id,
+                        registry_enrollment,
+                    }),
+            )
+    }
+
+    /// Callback after the create/deploy/init batch.
+    /// On failure, drops the instance entry and refunds the deposit to the owner.
+    /// On success, sends the creation fee to the treasury and enrolls the
+    /// coordinator in the registry if requested.
+    #[private]
+    pub fn on_coordinator_created(
+        &mut self,
+        pending: PendingCoordinator,
+    ) -> PromiseOrValue<Option<AccountId>> {
+        let PendingCoordinator {
+            account_id,
+            owner,
+            deposit,
+            creation_fee,
+            min_workers,
+            max_workers,
+            registry_contract_id,
+            registry_enrollment,
+        } = pending;
+        if matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            env::log_str(&format!("Created coordinator {} for {}", account_id, owner));
+            if let (false, Some(treasury)) = (creation_fee.is_zero(), self.treasury.clone()) {
+                Promise::new(treasury).transfer(creation_fee);
+            }
+            let (Some(enrollment), Some(registry_id)) =
+                (registry_enrollment, registry_contract_id)
+            else {
+                return PromiseOrValue::Value(Some(account_id));
+            };
+            let register_args = json!({
+                "owner": owner,
+                "registration": {
+                    "coordinator_did": enrollment.coordinator_did,
+                    "endpoint_url": enrollment.endpoint_url,
+                    "cvm_id": enrollment.cvm_id,
+                    "codehash": enrollment.codehash,
+                    "min_workers": min_workers,
+                    "max_workers": max_workers,
+                },
+            })
+            .to_string()
+            .into_bytes();
+            return PromiseOrValue::Promise(
+                Promise::new(registry_id)
+                    .function_call(
+                        "register_coordinator_for".to_string(),
+                        register_args,
+                        enrollment.deposit,
+                        REGISTER_GAS,
+                    )
+                    .then(
+                        Self::ext(env::current_account_id())
+                            .with_static_gas(CALLBACK_GAS)
+                            .on_coordinator_enrolled(account_id, owner, enrollment.deposit),
+                    ),
+            );
+        }
+
+        self.coordinators.remove(&account_id);
+        env::log_str(&format!(
+            "Failed to create coordinator {}, refunding {} to {}",
+            account_id, deposit, owner
+        ));
+        Promise::new(owner).transfer(deposit);
+        PromiseOrValue::Value(None)
     }
 
-    /// View: get the coordinator WASM hash (sha256 hex) embedded in this factory.
+    /// Callback after registry enrollment. The coordinator exists either way;
+    /// on failure the registry deposit is refunded to the owner.
+    #[private]
+    pub fn on_coordinator_enrolled(
+        &mut self,
+        account_id: AccountId,
+        owner: AccountId,
+        deposit: NearToken,
+    ) -> Option<AccountId> {
+        if matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            env::log_str(&format!("Enrolled coordinator {} in registry", account_id));
+        } else {
+            env::log_str(&format!(
+                "Registry enrollment of {} failed, refunding {} to {}",
+                account_id, deposit, owner
+            ));
+            Promise::new(owner).transfer(deposit);
+        }
+        Some(account_id)
+    }
+
+    /// Upgrade a deployed coordinator to a stored code version (or the default).
+    /// Must be called by the coordinator's upgrade authority: its owner, or the
+    /// coordinator itself while it has a council. Attach ~200 TGas.
+    pub fn upgrade_coordinator(
+        &mut self,
+        account_id: AccountId,
+        code_hash: Option<String>,
+    ) -> Promise {
+        let instance = self
+            .coordinators
+            .get(&account_id)
+            .unwrap_or_else(|| env::panic_str("Coordinator not deployed by this factory"));
+        let code_hash = code_hash
+            .or_else(|| self.default_version.clone())
+            .unwrap_or_else(|| env::panic_str("No coordinator code uploaded"));
+        assert!(
+            self.code_versions.contains_key(&code_hash),
+            "Unknown code version: {}",
+            code_hash
+        );
+        assert!(
+            instance.wasm_version != code_hash,
+            "Coordinator already runs version {}",
+            code_hash
+        );
+
+        Promise::new(account_id.clone())
+            .function_call(
+                "get_upgrade_authority".to_string(),
+                Vec::new(),
+                NearToken::from_near(0),
+                GET_AUTHORITY_GAS,
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(UPGRADE_CALLBACK_GAS)
+                    .on_upgrade_authority_checked(
+                        account_id,
+                        env::predecessor_account_id(),
+                        code_hash,
+                    ),
+            )
+    }
+
+    /// Callback with the coordinator's current upgrade authority.
+    /// Pushes the code to `update_code` if the upgrade was requested by it.
+    #[private]
+    pub fn on_upgrade_authority_checked(
+        &mut self,
+        account_id: AccountId,
+        requested_by: AccountId,
+        code_hash: String,
+        #[callback_result] authority: Result<AccountId, PromiseError>,
+    ) -> Promise {
+        let authority = authority
+            .unwrap_or_else(|_| env::panic_str("Failed to read coordinator upgrade authority"));
+        assert!(
+            authority == requested_by,
+            "Only the coordinator's upgrade authority can upgrade. Authority: {}, Caller: {}",
+            authority,
+            requested_by
+        );
+        // Keep the tracked owner in sync with ownership transfers
+        if authority != account_id {
+            if let Some(instance) = self.coordinators.get_mut(&account_id) {
+                instance.owner = authority;
+            }
+        }
+        let code = self
+            .code_blobs
+            .get(&code_hash)
+            .unwrap_or_else(|| env::panic_str(&format!("Unknown code version: {}", code_hash)))
+            .clone();
+
+        Promise::new(account_id.clone())
+            .function_call(
+                "update_code".to_string(),
+                code,
+                NearToken::from_near(0),
+                UPDATE_CODE_GAS,
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(CALLBACK_GAS)
+                    .on_coordinator_upgraded(account_id, code_hash),
+            )
+    }
+
+    /// Callback after `update_code`. Records the new version on success.
+    #[private]
+    pub fn on_coordinator_upgraded(&mut self, account_id: AccountId, code_hash: String) -> bool {
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            env::log_str(&format!("Upgrade of {} to {} failed", account_id, code_hash));
+            return false;
+        }
+        if let Some(instance) = self.coordinators.get_mut(&account_id) {
+            instance.wasm_version = code_hash.clone();
+        }
+        env::log_str(&format!("Upgraded {} to {}", account_id, code_hash));
+        true
+    }
+
+    /// View: SHA-256 hash (hex) of the coordinator WASM version deployed by default.
     /// Useful for verifying which coordinator version the factory deploys.
-    pub fn get_wasm_hash(&self) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        // Simple fingerprint — not cryptographic, just for display
-        let mut hasher = DefaultHasher::new();
-        COORDINATOR_WASM.hash(&mut hasher);
-        format!("{:x}", hasher.finish())
+    pub fn get_wasm_hash(&self) -> Option<String> {
+        self.default_version.clone()
+    }
+
+    /// View: all stored coordinator WASM versions.
+    pub fn get_code_versions(&self) -> Vec<CodeVersion> {
+        self.code_versions.values().cloned().collect()
+    }
+
+    /// View: registry new coordinators are enrolled in.
+    pub fn get_registry_contract_id(&self) -> Option<AccountId> {
+        self.registry_contract_id.clone()
     }
 
-    /// View: minimum deposit required (in yoctoNEAR as string).
+    /// View: factory owner.
+    pub fn get_owner(&self) -> AccountId {
+        self.owner.clone()
+    }
+
+    /// View: total deposit `create_coordinator` needs with the default code
+    /// version (in yoctoNEAR as string), including the creation fee but not a
+    /// registry deposit. Before any code is uploaded this is `min_deposit`
+    /// plus the fee. See `get_required_deposit` for other versions.
     pub fn get_min_deposit(&self) -> String {
-        MIN_DEPOSIT.as_yoctonear().to_string()
+        self.get_required_deposit(None)
+            .unwrap_or_else(|| self.min_deposit.saturating_add(self.creation_fee))
+            .as_yoctonear()
+            .to_string()
+    }
+
+    /// View: total deposit `create_coordinator` needs for a code version (or the
+    /// default), including the creation fee but not a registry deposit.
+    pub fn get_required_deposit(&self, code_hash: Option<String>) -> Option<NearToken> {
+        let version = code_hash.or_else(|| self.default_version.clone())?;
+        let size = self.code_versions.get(&version)?.size;
+        Some(self.account_deposit_for(size).saturating_add(self.creation_fee))
+    }
+
+    /// View: creation fee and the treasury it is sent to.
+    pub fn get_creation_fee(&self) -> (NearToken, Option<AccountId>) {
+        (self.creation_fee, self.treasury.clone())
+    }
+
+    /// View: whether coordinator creation is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// View: look up a deployed coordinator instance.
+    pub fn get_coordinator(&self, account_id: AccountId) -> Option<CoordinatorInstance> {
+        self.coordinators.get(&account_id).cloned()
+    }
+
+    /// View: paginated list of all deployed coordinator instances.
+    pub fn get_coordinators(
+        &self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<CoordinatorInstance> {
+        let from = from_index.unwrap_or(0) as usize;
+        let limit = limit.unwrap_or(self.coordinators.len()) as usize;
+        self.coordinators
+            .values()
+            .skip(from)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// View: paginated list of coordinator instances owned by `owner`.
+    pub fn get_coordinators_by_owner(
+        &self,
+        owner: AccountId,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<CoordinatorInstance> {
+        let from = from_index.unwrap_or(0) as usize;
+        let limit = limit.unwrap_or(self.coordinators.len()) as usize;
+        self.coordinators
+            .values()
+            .filter(|c| c.owner == owner)
+            .skip(from)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// View: reserved prefixes.
+    pub fn get_reserved_names(&self) -> Vec<String> {
+        self.reserved_names.iter().cloned().collect()
+    }
+
+    /// View: whether `prefix` can be used for a new coordinator.
+    pub fn is_prefix_available(&self, prefix: String) -> bool {
+        self.check_prefix(&prefix).is_ok()
+    }
+
+    /// View: number of deployed coordinator instances.
+    pub fn get_coordinator_count(&self) -> u32 {
+        self.coordinators.len()
+    }
+
+    // ========== INTERNAL ==========
+
+    /// Deposit a new coordinator account needs: storage for its WASM plus `min_deposit`.
+    fn account_deposit_for(&self, code_size: u64) -> NearToken {
+        env::storage_byte_cost()
+            .saturating_mul(code_size as u128)
+            .saturating_add(self.min_deposit)
+    }
+
+    /// Validate a coordinator prefix and return the sub-account it maps to.
+    /// Accounts that already exist on chain but aren't tracked here are only
+    /// caught by the create batch, which then refunds the caller.
+    fn check_prefix(&self, prefix: &str) -> Result<AccountId, String> {
+        if prefix.is_empty() {
+            return Err("Invalid prefix: must not be empty".to_string());
+        }
+        if prefix.contains('.') {
+            return Err("Invalid prefix: must be a single segment without dots".to_string());
+        }
+        if self.reserved_names.contains(prefix) {
+            return Err(format!("Prefix {} is reserved", prefix));
+        }
+        let account_id = AccountId::from_str(&format!("{}.{}", prefix, env::current_account_id()))
+            .map_err(|_| "Invalid prefix: cannot form valid account ID".to_string())?;
+        if self.coordinators.contains_key(&account_id) {
+            return Err(format!("Coordinator {} already exists", account_id));
+        }
+        Ok(account_id)
+    }
+
+    fn assert_owner(&self) {
+        assert!(
+            env::predecessor_account_id() == self.owner,
+            "Only owner can call this function"
+        );
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    const DEPOSIT: NearToken = NearToken::from_near(3);
+
+    fn get_context(predecessor: AccountId, deposit: NearToken) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id("coord-factory.testnet".parse().unwrap())
+            .predecessor_account_id(predecessor.clone())
+            .signer_account_id(predecessor)
+            .attached_deposit(deposit);
+        builder
+    }
+
+    const TEST_WASM: &[u8] = b"\0asm-coordinator-v1";
+
+    fn upload(factory: &mut CoordinatorFactory, code: &[u8]) -> String {
+        let mut context = get_context(accounts(0), NearToken::from_near(0)).build();
+        context.input = code.to_vec();
+        testing_env!(context);
+        let hash = factory.upload_code();
+        testing_env!(get_context(accounts(0), DEPOSIT).build());
+        hash
+    }
+
+    fn setup_factory() -> CoordinatorFactory {
+        testing_env!(get_context(accounts(0), DEPOSIT).build());
+        let mut factory = CoordinatorFactory::new(accounts(0));
+        upload(&mut factory, TEST_WASM);
+        factory
+    }
+
+    #[test]
+    fn test_upload_code_sha256() {
+        let mut factory = setup_factory();
+        let expected = hex_encode(&env::sha256(TEST_WASM));
+        assert_eq!(factory.get_wasm_hash(), Some(expected.clone()));
+        assert_eq!(expected.len(), 64);
+
+        let versions = factory.get_code_versions();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].size, TEST_WASM.len() as u64);
+
+        // Uploading again is idempotent
+        assert_eq!(upload(&mut factory, TEST_WASM), expected);
+        assert_eq!(factory.get_code_versions().len(), 1);
+    }
+
+    #[test]
+    fn test_set_default_version_and_create_with_version() {
+        let mut factory = setup_factory();
+        let v1 = factory.get_wasm_hash().unwrap();
+        let v2 = upload(&mut factory, b"\0asm-coordinator-v2");
+        // First upload stays default until changed
+        assert_eq!(factory.get_wasm_hash(), Some(v1.clone()));
+
+        factory.set_default_version(v2.clone());
+        assert_eq!(factory.get_wasm_hash(), Some(v2.clone()));
+
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        factory.create_coordinator(
+            "bob-dao".parse().unwrap(),
+            1,
+            5,
+            None,
+            Some(v1.clone()),
+            None,
+        );
+        assert_eq!(factory.get_coordinator(alice_dao()).unwrap().wasm_version, v2);
+        assert_eq!(
+            factory
+                .get_coordinator("bob-dao.coord-factory.testnet".parse().unwrap())
+                .unwrap()
+                .wasm_version,
+            v1
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_upload_code_non_owner() {
+        let mut factory = setup_factory();
+        let mut context = get_context(accounts(1), NearToken::from_near(0)).build();
+        context.input = b"\0asm-evil".to_vec();
+        testing_env!(context);
+        factory.upload_code();
+    }
+
+    #[test]
+    #[should_panic(expected = "No coordinator code uploaded")]
+    fn test_create_coordinator_without_code() {
+        testing_env!(get_context(accounts(0), DEPOSIT).build());
+        let mut factory = CoordinatorFactory::new(accounts(0));
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown code version")]
+    fn test_create_coordinator_unknown_version() {
+        let mut factory = setup_factory();
+        factory.create_coordinator(
+            "alice-dao".parse().unwrap(),
+            1,
+            5,
+            None,
+            Some("deadbeef".to_string()),
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot remove the default code version")]
+    fn test_remove_default_code() {
+        let mut factory = setup_factory();
+        let v1 = factory.get_wasm_hash().unwrap();
+        factory.remove_code(v1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinator not deployed by this factory")]
+    fn test_upgrade_unknown_coordinator() {
+        let mut factory = setup_factory();
+        factory.upgrade_coordinator(alice_dao(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinator already runs version")]
+    fn test_upgrade_to_same_version() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        factory.upgrade_coordinator(alice_dao(), None);
+    }
+
+    #[test]
+    fn test_upgrade_flow() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        let v2 = upload(&mut factory, b"\0asm-coordinator-v2");
+        factory.upgrade_coordinator(alice_dao(), Some(v2.clone()));
+
+        // Ownership was transferred to accounts(1) on the coordinator
+        factory.on_upgrade_authority_checked(alice_dao(), accounts(1), v2.clone(), Ok(accounts(1)));
+        assert_eq!(factory.get_coordinator(alice_dao()).unwrap().owner, accounts(1));
+
+        set_promise_result(PromiseResult::Successful(vec![]));
+        assert!(factory.on_coordinator_upgraded(alice_dao(), v2.clone()));
+        assert_eq!(factory.get_coordinator(alice_dao()).unwrap().wasm_version, v2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the coordinator's upgrade authority can upgrade")]
+    fn test_upgrade_rejected_for_non_owner() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        let v2 = upload(&mut factory, b"\0asm-coordinator-v2");
+        factory.on_upgrade_authority_checked(alice_dao(), accounts(2), v2, Ok(accounts(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the coordinator's upgrade authority can upgrade")]
+    fn test_upgrade_rejected_for_owner_under_council() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        let v2 = upload(&mut factory, b"\0asm-coordinator-v2");
+        // With a council the coordinator reports itself as the authority
+        factory.on_upgrade_authority_checked(alice_dao(), accounts(0), v2, Ok(alice_dao()));
+    }
+
+    #[test]
+    fn test_council_upgrade_keeps_tracked_owner() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        let v2 = upload(&mut factory, b"\0asm-coordinator-v2");
+        factory.on_upgrade_authority_checked(alice_dao(), alice_dao(), v2, Ok(alice_dao()));
+        assert_eq!(factory.get_coordinator(alice_dao()).unwrap().owner, accounts(0));
+    }
+
+    #[test]
+    fn test_failed_upgrade_keeps_version() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        let v1 = factory.get_wasm_hash().unwrap();
+        let v2 = upload(&mut factory, b"\0asm-coordinator-v2");
+
+        set_promise_result(PromiseResult::Failed);
+        assert!(!factory.on_coordinator_upgraded(alice_dao(), v2));
+        assert_eq!(factory.get_coordinator(alice_dao()).unwrap().wasm_version, v1);
+    }
+
+    fn enrollment(deposit: NearToken) -> RegistryEnrollment {
+        RegistryEnrollment {
+            coordinator_did: "did:key:z6MkCoordinator1".to_string(),
+            endpoint_url: "https://coord.example.com".to_string(),
+            cvm_id: "cvm-coord-1".to_string(),
+            codehash: "test_codehash".to_string(),
+            deposit,
+        }
+    }
+
+    /// Callback args for alice's "alice-dao" coordinator with bounds 1..=5
+    fn pending(
+        creation_fee: NearToken,
+        registry_enrollment: Option<RegistryEnrollment>,
+    ) -> PendingCoordinator {
+        PendingCoordinator {
+            account_id: alice_dao(),
+            owner: accounts(0),
+            deposit: DEPOSIT,
+            creation_fee,
+            min_workers: 1,
+            max_workers: 5,
+            registry_contract_id: Some("registry.testnet".parse().unwrap()),
+            registry_enrollment,
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "No registry configured for enrollment")]
+    fn test_enrollment_requires_registry() {
+        let mut factory = setup_factory();
+        testing_env!(get_context(accounts(0), NearToken::from_near(4)).build());
+        factory.create_coordinator(
+            "alice-dao".parse().unwrap(),
+            1,
+            5,
+            None,
+            None,
+            Some(enrollment(NearToken::from_millinear(100))),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Minimum deposit is")]
+    fn test_enrollment_deposit_not_counted_for_account() {
+        let mut factory = setup_factory();
+        factory.set_registry_contract_id(Some("registry.testnet".parse().unwrap()));
+        factory.set_min_deposit(DEPOSIT);
+        // 3 NEAR attached, but 0.1 NEAR of it is for the registry
+        factory.create_coordinator(
+            "alice-dao".parse().unwrap(),
+            1,
+            5,
+            None,
+            None,
+            Some(enrollment(NearToken::from_millinear(100))),
+        );
+    }
+
+    #[test]
+    fn test_enrollment_registers_with_owner() {
+        let mut factory = setup_factory();
+        // Enrollment goes to the registry resolved at creation, even if the
+        // factory's default changed while the batch was in flight
+        factory.set_registry_contract_id(Some("other-registry.testnet".parse().unwrap()));
+
+        set_promise_result(PromiseResult::Successful(vec![]));
+        let result = factory.on_coordinator_created(pending(
+            NearToken::from_near(0),
+            Some(enrollment(NearToken::from_millinear(100))),
+        ));
+        assert!(matches!(result, PromiseOrValue::Promise(_)));
+        drop(result);
+
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        let registry_call = receipts
+            .iter()
+            .find(|r| r.receiver_id.as_str() == "registry.testnet")
+            .expect("registry call");
+        match &registry_call.actions[0] {
+            near_sdk::mock::MockAction::FunctionCallWeight {
+                method_name,
+                args,
+                attached_deposit,
+                ..
+            } => {
+                assert_eq!(method_name, b"register_coordinator_for");
+                assert_eq!(*attached_deposit, NearToken::from_millinear(100));
+                let args: serde_json::Value = serde_json::from_slice(args).unwrap();
+                assert_eq!(args["owner"], accounts(0).as_str());
+                assert_eq!(args["registration"]["max_workers"], 5);
+            }
+            other => panic!("unexpected action {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_failed_enrollment_refunds_registry_deposit() {
+        let mut factory = setup_factory();
+        set_promise_result(PromiseResult::Failed);
+        let result = factory.on_coordinator_enrolled(
+            alice_dao(),
+            accounts(0),
+            NearToken::from_millinear(100),
+        );
+        assert_eq!(result, Some(alice_dao()));
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(0));
+    }
+
+    fn set_promise_result(result: PromiseResult) {
+        testing_env!(
+            get_context("coord-factory.testnet".parse().unwrap(), NearToken::from_near(0)).build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+    }
+
+    fn alice_dao() -> AccountId {
+        "alice-dao.coord-factory.testnet".parse().unwrap()
+    }
+
+    #[test]
+    fn test_create_coordinator_with_config() {
+        let mut factory = setup_factory();
+        factory.create_coordinator(
+            "alice-dao".parse().unwrap(),
+            1,
+            5,
+            Some(CoordinatorSettings {
+                manifesto_text: Some("We vote for good things.".to_string()),
+                approved_codehashes: Some(vec!["test_codehash".to_string()]),
+                registry_contract_id: Some("registry.testnet".parse().unwrap()),
+            }),
+            None,
+            None,
+        );
+
+        let instance = factory.get_coordinator(alice_dao()).unwrap();
+        assert_eq!(instance.owner, accounts(0));
+        assert_eq!(Some(instance.wasm_version), factory.get_wasm_hash());
+        assert_eq!(factory.get_coordinator_count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid prefix: must be a single segment without dots")]
+    fn test_create_coordinator_nested_prefix() {
+        let mut factory = setup_factory();
+        let prefix: AccountId = "sub.alice-dao".parse().unwrap();
+        factory.create_coordinator(prefix, 1, 5, None, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Prefix admin is reserved")]
+    fn test_create_coordinator_reserved_prefix() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("admin".parse().unwrap(), 1, 5, None, None, None);
+    }
+
+    #[test]
+    fn test_reserved_names_and_availability() {
+        let mut factory = setup_factory();
+        assert!(!factory.is_prefix_available("admin".to_string()));
+        assert!(!factory.is_prefix_available("a.b".to_string()));
+        assert!(!factory.is_prefix_available("Bad_Name!".to_string()));
+        assert!(factory.is_prefix_available("alice-dao".to_string()));
+
+        factory.add_reserved_name("alice-dao".to_string());
+        assert!(!factory.is_prefix_available("alice-dao".to_string()));
+        factory.remove_reserved_name("alice-dao".to_string());
+        factory.remove_reserved_name("admin".to_string());
+        assert!(factory.is_prefix_available("admin".to_string()));
+        assert!(!factory.get_reserved_names().contains(&"admin".to_string()));
+
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        assert!(!factory.is_prefix_available("alice-dao".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_add_reserved_name_non_owner() {
+        let mut factory = setup_factory();
+        testing_env!(get_context(accounts(1), NearToken::from_near(0)).build());
+        factory.add_reserved_name("bob".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "already exists")]
+    fn test_create_coordinator_duplicate_prefix() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+    }
+
+    #[test]
+    fn test_on_coordinator_created_success_keeps_entry() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+
+        set_promise_result(PromiseResult::Successful(vec![]));
+        let result = factory.on_coordinator_created(pending(NearToken::from_near(0), None));
+        assert!(matches!(result, PromiseOrValue::Value(Some(id)) if id == alice_dao()));
+        assert!(factory.get_coordinator(alice_dao()).is_some());
+    }
+
+    #[test]
+    fn test_on_coordinator_created_failure_refunds() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+
+        set_promise_result(PromiseResult::Failed);
+        let result = factory.on_coordinator_created(pending(NearToken::from_near(0), None));
+        assert!(matches!(result, PromiseOrValue::Value(None)));
+        assert!(factory.get_coordinator(alice_dao()).is_none());
+
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(0));
+    }
+
+    #[test]
+    fn test_get_coordinators_pagination() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        factory.create_coordinator("bob-dao".parse().unwrap(), 1, 5, None, None, None);
+        testing_env!(get_context(accounts(1), DEPOSIT).build());
+        factory.create_coordinator("carol-dao".parse().unwrap(), 1, 5, None, None, None);
+
+        assert_eq!(factory.get_coordinators(None, None).len(), 3);
+        let page = factory.get_coordinators(Some(1), Some(1));
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].account_id.as_str(), "bob-dao.coord-factory.testnet");
+
+        assert_eq!(factory.get_coordinators_by_owner(accounts(0), None, None).len(), 2);
+        let carol = factory.get_coordinators_by_owner(accounts(1), None, None);
+        assert_eq!(carol.len(), 1);
+        assert_eq!(carol[0].account_id.as_str(), "carol-dao.coord-factory.testnet");
+    }
+
+    #[test]
+    #[should_panic(expected = "Minimum deposit is")]
+    fn test_create_coordinator_low_deposit() {
+        let mut factory = setup_factory();
+        testing_env!(get_context(accounts(0), NearToken::from_near(1)).build());
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+    }
+
+    #[test]
+    fn test_required_deposit_covers_wasm_storage() {
+        let mut factory = setup_factory();
+        let storage = env::storage_byte_cost().saturating_mul(TEST_WASM.len() as u128);
+        assert_eq!(
+            factory.get_required_deposit(None),
+            Some(DEFAULT_MIN_DEPOSIT.saturating_add(storage))
+        );
+        assert_eq!(factory.get_required_deposit(Some("deadbeef".to_string())), None);
+
+        factory.set_min_deposit(NearToken::from_near(2));
+        factory.set_creation_fee(NearToken::from_millinear(500), Some(accounts(3)));
+        assert_eq!(
+            factory.get_required_deposit(None),
+            Some(NearToken::from_millinear(2500).saturating_add(storage))
+        );
+        assert_eq!(
+            factory.get_min_deposit(),
+            NearToken::from_millinear(2500).saturating_add(storage).as_yoctonear().to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Minimum deposit is")]
+    fn test_create_coordinator_deposit_excludes_fee() {
+        let mut factory = setup_factory();
+        factory.set_min_deposit(NearToken::from_near(2));
+        factory.set_creation_fee(NearToken::from_near(1), Some(accounts(3)));
+        // 3 NEAR attached, but 1 NEAR of it is the fee
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "A treasury is required for a non-zero creation fee")]
+    fn test_creation_fee_requires_treasury() {
+        let mut factory = setup_factory();
+        factory.set_creation_fee(NearToken::from_near(1), None);
+    }
+
+    #[test]
+    fn test_creation_fee_sent_to_treasury_on_success() {
+        let mut factory = setup_factory();
+        factory.set_creation_fee(NearToken::from_near(1), Some(accounts(3)));
+
+        set_promise_result(PromiseResult::Successful(vec![]));
+        factory.on_coordinator_created(pending(NearToken::from_near(1), None));
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Factory is paused")]
+    fn test_create_coordinator_paused() {
+        let mut factory = setup_factory();
+        factory.pause();
+        assert!(factory.is_paused());
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+    }
+
+    #[test]
+    fn test_unpause_allows_creation() {
+        let mut factory = setup_factory();
+        factory.pause();
+        factory.unpause();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 1, 5, None, None, None);
+        assert_eq!(factory.get_coordinator_count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_pause_non_owner() {
+        let mut factory = setup_factory();
+        testing_env!(get_context(accounts(1), NearToken::from_near(0)).build());
+        factory.pause();
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this function")]
+    fn test_set_min_deposit_non_owner() {
+        let mut factory = setup_factory();
+        testing_env!(get_context(accounts(1), NearToken::from_near(0)).build());
+        factory.set_min_deposit(NearToken::from_near(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "max_workers must be >= min_workers")]
+    fn test_create_coordinator_invalid_bounds() {
+        let mut factory = setup_factory();
+        factory.create_coordinator("alice-dao".parse().unwrap(), 3, 2, None, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Manifesto text needs to be under 10,000 characters")]
+    fn test_create_coordinator_manifesto_too_long() {
+        let mut factory = setup_factory();
+        factory.create_coordinator(
+            "alice-dao".parse().unwrap(),
+            1,
+            5,
+            Some(CoordinatorSettings {
+                manifesto_text: Some("a".repeat(MAX_MANIFESTO_LEN + 1)),
+                ..Default::default()
+            }),
+            None,
+            None,
+        );
     }
 }
@@ -26,6 +26,7 @@ export interface CoordinatorJobState {
   storachaPrivateKey?: string;
   phalaEndpoint?: string;
   cvmId?: string;
+  codehash?: string;
   dashboardUrl?: string;
   displayName?: string;
   nearAccount?: string;
@@ -106,13 +106,14 @@ export default function CoordinatorBuyPage() {
 
   /** Tx #2: Registry register */
   const handleRegistryRegister = useCallback(async () => {
-    if (!job?.coordinatorDid || !job?.phalaEndpoint || !job?.cvmId) return;
+    if (!job?.coordinatorDid || !job?.phalaEndpoint || !job?.cvmId || !job?.codehash) return;
     const addr = contractAddress || job.contractAddress || "";
 
     const tx = buildRegisterCoordinatorAction({
       coordinatorDid: job.coordinatorDid,
       endpointUrl: job.phalaEndpoint,
       cvmId: job.cvmId,
+      codehash: job.codehash,
       minWorkers: job.minWorkers ?? 1,
       maxWorkers: job.maxWorkers ?? 10,
     });
@@ -37,6 +37,7 @@ export function buildRegisterCoordinatorAction(params: {
   coordinatorDid: string;
   endpointUrl: string;
   cvmId: string;
+  codehash: string;
   minWorkers: number;
   maxWorkers: number;
 }) {
@@ -51,6 +52,7 @@ export function buildRegisterCoordinatorAction(params: {
             coordinator_did: params.coordinatorDid,
             endpoint_url: params.endpointUrl,
             cvm_id: params.cvmId,
+            codehash: params.codehash,
             min_workers: params.minWorkers,
             max_workers: params.maxWorkers,
           },
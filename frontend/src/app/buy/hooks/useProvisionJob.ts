@@ -25,6 +25,7 @@ export interface ProvisionJobState {
   storachaPrivateKey?: string;
   phalaEndpoint?: string;
   cvmId?: string;
+  codehash?: string;
   dashboardUrl?: string;
   coordinatorDid?: string;
   displayName?: string;
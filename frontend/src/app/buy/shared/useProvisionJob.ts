@@ -24,6 +24,7 @@ export interface BaseJobState {
   step: string;
   phalaEndpoint?: string;
   cvmId?: string;
+  codehash?: string;
   dashboardUrl?: string;
   displayName?: string;
   nearAccount?: string;
@@ -57,7 +57,7 @@ export default function BuyPage() {
   );
 
   const handleSign = useCallback(async () => {
-    if (!job?.workerDid || !job?.phalaEndpoint || !job?.cvmId) return;
+    if (!job?.workerDid || !job?.phalaEndpoint || !job?.cvmId || !job?.codehash) return;
 
     const REGISTRY_CONTRACT_ID =
       process.env.NEXT_PUBLIC_REGISTRY_CONTRACT_ID ||
@@ -76,6 +76,7 @@ export default function BuyPage() {
                 worker_did: job.workerDid,
                 endpoint_url: job.phalaEndpoint,
                 cvm_id: job.cvmId,
+                codehash: job.codehash,
               },
               gas: "200000000000000",
               deposit: "100000000000000000000000",
@@ -14,6 +14,7 @@ export interface RegisterWorkerParams {
   workerDid: string;
   endpointUrl: string;
   cvmId: string;
+  codehash: string;
 }
 
 /**
@@ -33,6 +34,7 @@ export function buildRegisterWorkerAction(params: RegisterWorkerParams) {
             worker_did: params.workerDid,
             endpoint_url: params.endpointUrl,
             cvm_id: params.cvmId,
+            codehash: params.codehash,
           },
           gas: "200000000000000", // 200 TGas
           deposit: "100000000000000000000000", // 0.1 NEAR
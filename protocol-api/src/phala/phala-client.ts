@@ -17,6 +17,8 @@ export interface DeployCvmResult {
   status: string;
   dashboardUrl: string;
   appId: string;
+  /** Compose hash of the deployed app — the codehash the registry checks */
+  composeHash: string;
   endpointUrl?: string;
   deterministicUrl?: string;
 }
@@ -53,6 +55,11 @@ export async function deployCvm(
   });
 
   console.log(`[phala] Provisioned: app_id=${provision.app_id}, compose_hash=${provision.compose_hash}`);
+  // The compose hash is the codehash the registry checks; without it the agent can never register
+  const composeHash = provision.compose_hash;
+  if (!composeHash) {
+    throw new Error(`Phala returned no compose hash for "${name}"`);
+  }
 
   // Step 2: Encrypt env vars
   let encryptedEnv: string | undefined;
@@ -65,7 +72,7 @@ export async function deployCvm(
   console.log(`[phala] Committing CVM...`);
   const result = await client.commitCvmProvision({
     app_id: provision.app_id ?? '',
-    compose_hash: provision.compose_hash ?? '',
+    compose_hash: composeHash,
     encrypted_env: encryptedEnv,
     env_keys: allowedEnvKeys,
   });
@@ -93,6 +100,7 @@ export async function deployCvm(
         status: 'running',
         dashboardUrl: `${CLOUD_URL}/dashboard/cvms/${vmUuid}`,
         appId,
+        composeHash,
         endpointUrl: apiUrl,
       };
     }
@@ -103,6 +111,7 @@ export async function deployCvm(
     status: endpointUrl ? 'running' : 'deploying',
     dashboardUrl: `${CLOUD_URL}/dashboard/cvms/${vmUuid}`,
     appId,
+    composeHash,
     endpointUrl,
     deterministicUrl,
   };
@@ -39,6 +39,7 @@ interface ProvisionJob {
   step: string;
   phalaEndpoint?: string;
   cvmId?: string;
+  codehash?: string;
   dashboardUrl?: string;
   error?: string;
   createdAt: number;
@@ -281,6 +282,7 @@ async function provisionWorker(job: ProvisionJob): Promise<void> {
 
     const result = await deployCvm(phalaApiKey, cvmName, composeContent, envVars);
     job.cvmId = result.cvmId;
+    job.codehash = result.composeHash;
     job.dashboardUrl = result.dashboardUrl;
     console.log(`[provision] CVM created: ${result.cvmId}`);
 
@@ -405,6 +407,7 @@ async function provisionCoordinator(job: ProvisionJob): Promise<void> {
 
     const result = await deployCvm(phalaApiKey, cvmName, composeContent, envVars, 3000);
     job.cvmId = result.cvmId;
+    job.codehash = result.composeHash;
     job.dashboardUrl = result.dashboardUrl;
     console.log(`[provision/coordinator] CVM created: ${result.cvmId}`);
 
@@ -498,6 +501,7 @@ provision.get('/status/:jobId', (c) => {
     storachaPrivateKey: revealSecrets ? job.storachaPrivateKey : undefined,
     phalaEndpoint: job.phalaEndpoint,
     cvmId: job.cvmId,
+    codehash: job.codehash,
     dashboardUrl: job.dashboardUrl,
     coordinatorDid: job.coordinatorDid,
     displayName: job.displayName,
@@ -1,10 +1,18 @@
 use near_sdk::{
     env, near, require,
-    store::IterableMap,
+    store::{IterableMap, IterableSet, LookupMap},
     AccountId, BorshStorageKey, NearToken, PanicOnDefault,
 };
 
 const DEFAULT_MIN_DEPOSIT: NearToken = NearToken::from_millinear(100); // 0.1 NEAR
+const NANOS_PER_HOUR: u64 = 3_600_000_000_000;
+/// Agents without a heartbeat for this long are hidden from views
+const DEFAULT_STALENESS_WINDOW: u64 = 24 * NANOS_PER_HOUR;
+/// Agents without a heartbeat for this long can be deactivated by `reap_stale`
+const DEFAULT_REAP_AFTER: u64 = 7 * 24 * NANOS_PER_HOUR;
+/// Upper bound on records examined by a single `reap_stale` call
+const MAX_REAP_BATCH: u32 = 100;
+const MAX_ENDPOINT_URL_LEN: usize = 256;
 
 #[derive(BorshStorageKey)]
 #[near]
@@ -13,8 +21,31 @@ pub enum StorageKey {
     _DeprecatedWorkers,      // ordinal 1 — V1 format (dead)
     _DeprecatedCoordinatorsV2, // ordinal 2 — V2 format (dead)
     _DeprecatedWorkersV2,    // ordinal 3 — V2 format (dead)
-    WorkersByDid,            // ordinal 4 — V3 primary index
-    CoordinatorsByDid,       // ordinal 5 — V3 primary index
+    _DeprecatedWorkersV3,    // ordinal 4 — V3 format (dead)
+    _DeprecatedCoordinatorsV3, // ordinal 5 — V3 format (dead)
+    WorkersByDid,            // ordinal 6 — V4 primary index (adds codehash)
+    CoordinatorsByDid,       // ordinal 7 — V4 primary index (adds codehash)
+    ApprovedCodehashes,      // ordinal 8
+    Moderators,              // ordinal 9
+    WorkerDidBySeq,          // ordinal 10
+    TrustedFactories,        // ordinal 11
+}
+
+/// Kind of service an agent exposes at an endpoint
+#[near(serializers = [json, borsh])]
+#[derive(Clone, PartialEq, Debug)]
+pub enum EndpointKind {
+    Api,    // HTTP API (mirrored in `endpoint_url`)
+    Stream, // SSE / streaming updates
+    Health, // Health check
+}
+
+/// A typed agent endpoint
+#[near(serializers = [json, borsh])]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Endpoint {
+    pub kind: EndpointKind,
+    pub url: String,
 }
 
 /// A registered worker agent, keyed by `worker_did`
@@ -24,10 +55,24 @@ pub struct WorkerRecord {
     pub account_id: AccountId,
     pub coordinator_did: String,
     pub worker_did: String,
+    /// Stable numeric ID assigned at first registration, kept across upserts
+    pub worker_seq: u64,
     pub endpoint_url: String,
+    /// All typed endpoints, at most one per kind. Always contains the `Api` endpoint.
+    pub endpoints: Vec<Endpoint>,
     pub cvm_id: String,
+    /// Phala TEE compose/measurement hash, must be in `approved_codehashes`
+    pub codehash: String,
     pub registered_at: u64,
+    /// Block timestamp of the last registration, endpoint or status change
+    pub updated_at: u64,
+    /// Block timestamp of the last heartbeat (or registration)
+    pub last_seen: u64,
     pub is_active: bool,
+    /// Set when the record is deactivated, cleared on reactivation
+    pub deactivated_at: Option<u64>,
+    /// Account that deactivated the record, `None` when reaped. Cleared on reactivation.
+    pub deactivated_by: Option<AccountId>,
 }
 
 /// A registered coordinator, keyed by `coordinator_did`
@@ -37,11 +82,23 @@ pub struct CoordinatorRecord {
     pub account_id: AccountId,
     pub coordinator_did: String,
     pub endpoint_url: String,
+    /// All typed endpoints, at most one per kind. Always contains the `Api` endpoint.
+    pub endpoints: Vec<Endpoint>,
     pub cvm_id: String,
+    /// Phala TEE compose/measurement hash, must be in `approved_codehashes`
+    pub codehash: String,
     pub min_workers: u8,
     pub max_workers: u8,
     pub registered_at: u64,
+    /// Block timestamp of the last registration, endpoint or status change
+    pub updated_at: u64,
+    /// Block timestamp of the last heartbeat (or registration)
+    pub last_seen: u64,
     pub is_active: bool,
+    /// Set when the record is deactivated, cleared on reactivation
+    pub deactivated_at: Option<u64>,
+    /// Account that deactivated the record, `None` when reaped. Cleared on reactivation.
+    pub deactivated_by: Option<AccountId>,
 }
 
 #[near(contract_state)]
@@ -51,7 +108,50 @@ pub struct RegistryContract {
     pub workers_by_did: IterableMap<String, WorkerRecord>,
     pub coordinators_by_did: IterableMap<String, CoordinatorRecord>,
     pub min_deposit: NearToken,
+    /// Last assigned `worker_seq`; IDs start at 1 and are never reused
     pub next_worker_seq: u64,
+    /// Reverse index `worker_seq` -> `worker_did`
+    pub worker_did_by_seq: LookupMap<u64, String>,
+    pub approved_codehashes: IterableSet<String>,
+    /// Heartbeat age (ns) after which agents are hidden from views
+    pub staleness_window: u64,
+    /// Heartbeat age (ns) after which `reap_stale` deactivates agents
+    pub reap_after: u64,
+    /// Accept `http://` endpoints (testnets only)
+    pub allow_http: bool,
+    /// Proposed admin awaiting `accept_admin`
+    pub pending_admin: Option<AccountId>,
+    /// Accounts that may deactivate any record, but not change settings
+    pub moderators: IterableSet<AccountId>,
+    /// Coordinator factories allowed to register coordinators on behalf of their owners
+    pub trusted_factories: IterableSet<AccountId>,
+}
+
+/// Current role assignments
+#[near(serializers = [json])]
+pub struct Roles {
+    pub admin: AccountId,
+    pub pending_admin: Option<AccountId>,
+    pub moderators: Vec<AccountId>,
+}
+
+/// Outcome of a `reap_stale` batch
+#[near(serializers = [json])]
+pub struct ReapResult {
+    pub deactivated: u32,
+    /// Index to pass as `from_index` for the next batch, `None` when done
+    pub next_index: Option<u32>,
+}
+
+/// Coordinator listing submitted by a trusted factory via `register_coordinator_for`
+#[near(serializers = [json])]
+pub struct CoordinatorRegistration {
+    pub coordinator_did: String,
+    pub endpoint_url: String,
+    pub cvm_id: String,
+    pub codehash: String,
+    pub min_workers: u8,
+    pub max_workers: u8,
 }
 
 #[near]
@@ -65,6 +165,14 @@ impl RegistryContract {
             coordinators_by_did: IterableMap::new(StorageKey::CoordinatorsByDid),
             min_deposit: DEFAULT_MIN_DEPOSIT,
             next_worker_seq: 0,
+            worker_did_by_seq: LookupMap::new(StorageKey::WorkerDidBySeq),
+            approved_codehashes: IterableSet::new(StorageKey::ApprovedCodehashes),
+            staleness_window: DEFAULT_STALENESS_WINDOW,
+            reap_after: DEFAULT_REAP_AFTER,
+            allow_http: false,
+            pending_admin: None,
+            moderators: IterableSet::new(StorageKey::Moderators),
+            trusted_factories: IterableSet::new(StorageKey::TrustedFactories),
         }
     }
 
@@ -80,18 +188,81 @@ impl RegistryContract {
             coordinators_by_did: IterableMap::new(StorageKey::CoordinatorsByDid),
             min_deposit: DEFAULT_MIN_DEPOSIT,
             next_worker_seq: 0,
+            worker_did_by_seq: LookupMap::new(StorageKey::WorkerDidBySeq),
+            approved_codehashes: IterableSet::new(StorageKey::ApprovedCodehashes),
+            staleness_window: DEFAULT_STALENESS_WINDOW,
+            reap_after: DEFAULT_REAP_AFTER,
+            allow_http: false,
+            pending_admin: None,
+            moderators: IterableSet::new(StorageKey::Moderators),
+            trusted_factories: IterableSet::new(StorageKey::TrustedFactories),
         }
     }
 
     // ========== COORDINATOR REGISTRATION ==========
 
-    /// Register or update a coordinator. Requires minimum deposit.
+    /// Register or update a coordinator. Requires minimum deposit and an
+    /// admin-approved TEE codehash.
     #[payable]
     pub fn register_coordinator(
         &mut self,
         coordinator_did: String,
         endpoint_url: String,
         cvm_id: String,
+        codehash: String,
+        min_workers: u8,
+        max_workers: u8,
+    ) -> CoordinatorRecord {
+        self.upsert_coordinator(
+            env::predecessor_account_id(),
+            coordinator_did,
+            endpoint_url,
+            cvm_id,
+            codehash,
+            min_workers,
+            max_workers,
+        )
+    }
+
+    /// Register or update a coordinator on behalf of `owner`, who becomes the
+    /// record's account. Only callable by a trusted coordinator factory.
+    #[payable]
+    pub fn register_coordinator_for(
+        &mut self,
+        owner: AccountId,
+        registration: CoordinatorRegistration,
+    ) -> CoordinatorRecord {
+        require!(
+            self.trusted_factories.contains(&env::predecessor_account_id()),
+            "Only a trusted factory can register on behalf of an owner"
+        );
+        let CoordinatorRegistration {
+            coordinator_did,
+            endpoint_url,
+            cvm_id,
+            codehash,
+            min_workers,
+            max_workers,
+        } = registration;
+        self.upsert_coordinator(
+            owner,
+            coordinator_did,
+            endpoint_url,
+            cvm_id,
+            codehash,
+            min_workers,
+            max_workers,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn upsert_coordinator(
+        &mut self,
+        caller: AccountId,
+        coordinator_did: String,
+        endpoint_url: String,
+        cvm_id: String,
+        codehash: String,
         min_workers: u8,
         max_workers: u8,
     ) -> CoordinatorRecord {
@@ -109,37 +280,57 @@ impl RegistryContract {
             "min_workers must be <= max_workers"
         );
         require!(max_workers > 0, "max_workers must be > 0");
+        self.require_approved_codehash(&codehash);
+        self.validate_endpoint_url(&endpoint_url);
 
-        let caller = env::predecessor_account_id();
-
-        // Upsert: update existing or insert new
+        // Upsert: update existing or insert new, keeping non-API endpoints
+        let now = env::block_timestamp();
+        let mut endpoints = Vec::new();
+        let mut registered_at = now;
         if let Some(existing) = self.coordinators_by_did.get(&coordinator_did) {
             require!(
                 caller == existing.account_id || caller == self.admin,
                 "Only the original registrant or admin can update"
             );
+            require!(
+                caller == self.admin || !existing.is_moderated(),
+                "Record was deactivated by moderation"
+            );
+            endpoints = existing.endpoints.clone();
+            registered_at = existing.registered_at;
         }
+        set_api_endpoint(&mut endpoints, &endpoint_url);
 
         let record = CoordinatorRecord {
             account_id: caller,
             coordinator_did: coordinator_did.clone(),
             endpoint_url,
+            endpoints,
             cvm_id,
+            codehash,
             min_workers,
             max_workers,
-            registered_at: env::block_timestamp(),
+            registered_at,
+            updated_at: now,
+            last_seen: now,
             is_active: true,
+            deactivated_at: None,
+            deactivated_by: None,
         };
 
         self.coordinators_by_did
             .insert(coordinator_did.clone(), record.clone());
-        env::log_str(&format!("Registered coordinator: {}", coordinator_did));
+        env::log_str(&format!(
+            "Registered coordinator: {} (account: {})",
+            coordinator_did, record.account_id
+        ));
         record
     }
 
     // ========== WORKER REGISTRATION ==========
 
-    /// Register or update a worker. Requires minimum deposit.
+    /// Register or update a worker. Requires minimum deposit and an
+    /// admin-approved TEE codehash.
     /// The referenced coordinator_did must exist and be active.
     #[payable]
     pub fn register_worker(
@@ -148,6 +339,7 @@ impl RegistryContract {
         worker_did: String,
         endpoint_url: String,
         cvm_id: String,
+        codehash: String,
     ) -> WorkerRecord {
         let deposit = env::attached_deposit();
         require!(
@@ -162,6 +354,8 @@ impl RegistryContract {
             worker_did.starts_with("did:"),
             "worker_did must start with 'did:'"
         );
+        self.require_approved_codehash(&codehash);
+        self.validate_endpoint_url(&endpoint_url);
 
         // Validate coordinator exists and is active
         let coordinator = self
@@ -169,40 +363,66 @@ impl RegistryContract {
             .get(&coordinator_did)
             .expect("Coordinator not found");
         require!(
-            coordinator.is_active,
+            self.is_coordinator_live(coordinator),
             "Coordinator is not active"
         );
 
         let caller = env::predecessor_account_id();
 
-        // Upsert: update existing or insert new
+        // Upsert: update existing or insert new, keeping non-API endpoints
+        let now = env::block_timestamp();
+        let mut endpoints = Vec::new();
+        let mut registered_at = now;
+        let mut worker_seq = None;
         if let Some(existing) = self.workers_by_did.get(&worker_did) {
             require!(
                 caller == existing.account_id || caller == self.admin,
                 "Only the original registrant or admin can update"
             );
+            require!(
+                caller == self.admin || !existing.is_moderated(),
+                "Record was deactivated by moderation"
+            );
+            endpoints = existing.endpoints.clone();
+            registered_at = existing.registered_at;
+            worker_seq = Some(existing.worker_seq);
         }
+        set_api_endpoint(&mut endpoints, &endpoint_url);
+        let worker_seq = worker_seq.unwrap_or_else(|| {
+            self.next_worker_seq += 1;
+            self.worker_did_by_seq
+                .insert(self.next_worker_seq, worker_did.clone());
+            self.next_worker_seq
+        });
 
         let record = WorkerRecord {
             account_id: caller,
             coordinator_did,
             worker_did: worker_did.clone(),
+            worker_seq,
             endpoint_url,
+            endpoints,
             cvm_id,
-            registered_at: env::block_timestamp(),
+            codehash,
+            registered_at,
+            updated_at: now,
+            last_seen: now,
             is_active: true,
+            deactivated_at: None,
+            deactivated_by: None,
         };
 
         self.workers_by_did
             .insert(worker_did.clone(), record.clone());
-        env::log_str(&format!("Registered worker: {}", worker_did));
+        env::log_str(&format!("Registered worker #{}: {}", worker_seq, worker_did));
         record
     }
 
     // ========== MUTATORS ==========
 
-    /// Update a worker's endpoint URL (only the worker's account_id or admin)
+    /// Update a worker's API endpoint URL (only the worker's account_id or admin)
     pub fn update_worker_endpoint(&mut self, worker_did: String, endpoint_url: String) {
+        self.validate_endpoint_url(&endpoint_url);
         let entry = self
             .workers_by_did
             .get_mut(&worker_did)
@@ -212,12 +432,34 @@ impl RegistryContract {
             caller == entry.account_id || caller == self.admin,
             "Only worker owner or admin can update endpoint"
         );
+        set_api_endpoint(&mut entry.endpoints, &endpoint_url);
         entry.endpoint_url = endpoint_url;
+        entry.updated_at = env::block_timestamp();
         env::log_str(&format!("Updated endpoint for worker: {}", worker_did));
     }
 
-    /// Deactivate a worker (only the worker's account_id or admin)
-    pub fn deactivate_worker(&mut self, worker_did: String) {
+    /// Update a coordinator's API endpoint URL (only the coordinator's account_id or admin)
+    pub fn update_coordinator_endpoint(&mut self, coordinator_did: String, endpoint_url: String) {
+        self.validate_endpoint_url(&endpoint_url);
+        let entry = self
+            .coordinators_by_did
+            .get_mut(&coordinator_did)
+            .expect("Coordinator not found");
+        let caller = env::predecessor_account_id();
+        require!(
+            caller == entry.account_id || caller == self.admin,
+            "Only coordinator owner or admin can update endpoint"
+        );
+        set_api_endpoint(&mut entry.endpoints, &endpoint_url);
+        entry.endpoint_url = endpoint_url;
+        entry.updated_at = env::block_timestamp();
+        env::log_str(&format!("Updated endpoint for coordinator: {}", coordinator_did));
+    }
+
+    /// Replace all typed endpoints of a worker (only the worker's account_id or admin).
+    /// Must contain exactly one endpoint per kind used, including `Api`.
+    pub fn set_worker_endpoints(&mut self, worker_did: String, endpoints: Vec<Endpoint>) {
+        self.validate_endpoints(&endpoints);
         let entry = self
             .workers_by_did
             .get_mut(&worker_did)
@@ -225,13 +467,85 @@ impl RegistryContract {
         let caller = env::predecessor_account_id();
         require!(
             caller == entry.account_id || caller == self.admin,
-            "Only worker owner or admin can deactivate"
+            "Only worker owner or admin can update endpoint"
+        );
+        entry.endpoint_url = api_endpoint_url(&endpoints);
+        entry.endpoints = endpoints;
+        entry.updated_at = env::block_timestamp();
+        env::log_str(&format!("Updated endpoints for worker: {}", worker_did));
+    }
+
+    /// Replace all typed endpoints of a coordinator (only the coordinator's account_id or admin).
+    /// Must contain exactly one endpoint per kind used, including `Api`.
+    pub fn set_coordinator_endpoints(&mut self, coordinator_did: String, endpoints: Vec<Endpoint>) {
+        self.validate_endpoints(&endpoints);
+        let entry = self
+            .coordinators_by_did
+            .get_mut(&coordinator_did)
+            .expect("Coordinator not found");
+        let caller = env::predecessor_account_id();
+        require!(
+            caller == entry.account_id || caller == self.admin,
+            "Only coordinator owner or admin can update endpoint"
         );
-        entry.is_active = false;
+        entry.endpoint_url = api_endpoint_url(&endpoints);
+        entry.endpoints = endpoints;
+        entry.updated_at = env::block_timestamp();
+        env::log_str(&format!("Updated endpoints for coordinator: {}", coordinator_did));
+    }
+
+    /// Deactivate a worker (only the worker's account_id, admin or a moderator)
+    pub fn deactivate_worker(&mut self, worker_did: String) {
+        let entry = self
+            .workers_by_did
+            .get_mut(&worker_did)
+            .expect("Worker not found");
+        let caller = env::predecessor_account_id();
+        require!(
+            caller == entry.account_id || caller == self.admin || self.moderators.contains(&caller),
+            "Only worker owner, admin or moderator can deactivate"
+        );
+        entry.deactivate(env::block_timestamp(), Some(caller));
         env::log_str(&format!("Deactivated worker: {}", worker_did));
     }
 
-    /// Deactivate a coordinator (only the coordinator's account_id or admin)
+    /// Reactivate a deactivated worker (only the worker's account_id or admin).
+    /// Its coordinator must be active. A deactivation by admin or a moderator
+    /// can only be reversed by admin or a moderator.
+    pub fn reactivate_worker(&mut self, worker_did: String) {
+        let coordinator_did = self
+            .workers_by_did
+            .get(&worker_did)
+            .expect("Worker not found")
+            .coordinator_did
+            .clone();
+        require!(
+            self.coordinators_by_did
+                .get(&coordinator_did)
+                .is_some_and(|c| self.is_coordinator_live(c)),
+            "Coordinator is not active"
+        );
+        let caller = env::predecessor_account_id();
+        let is_moderator = caller == self.admin || self.moderators.contains(&caller);
+        let entry = self.workers_by_did.get_mut(&worker_did).unwrap();
+        if entry.is_moderated() {
+            require!(
+                is_moderator,
+                "Only admin or moderator can reverse a moderation deactivation"
+            );
+        } else {
+            require!(
+                caller == entry.account_id || caller == self.admin,
+                "Only worker owner or admin can reactivate"
+            );
+        }
+        entry.reactivate(env::block_timestamp());
+        env::log_str(&format!("Reactivated worker: {}", worker_did));
+    }
+
+    /// Deactivate a coordinator (only the coordinator's account_id, admin or a moderator).
+    /// Its workers show as inactive immediately; `reap_orphaned_workers`
+    /// persists their deactivation in batches.
     pub fn deactivate_coordinator(&mut self, coordinator_did: String) {
         let entry = self
             .coordinators_by_did
@@ -239,13 +553,228 @@ impl RegistryContract {
             .expect("Coordinator not found");
         let caller = env::predecessor_account_id();
         require!(
-            caller == entry.account_id || caller == self.admin,
-            "Only coordinator owner or admin can deactivate"
+            caller == entry.account_id || caller == self.admin || self.moderators.contains(&caller),
+            "Only coordinator owner, admin or moderator can deactivate"
         );
-        entry.is_active = false;
+        entry.deactivate(env::block_timestamp(), Some(caller));
         env::log_str(&format!("Deactivated coordinator: {}", coordinator_did));
     }
 
+    /// Reactivate a deactivated coordinator (only the coordinator's account_id or admin).
+    /// Its codehash must still be approved, and a deactivation by admin or a
+    /// moderator can only be reversed by admin or a moderator. Workers already
+    /// deactivated by `reap_orphaned_workers` stay inactive until they are
+    /// reactivated individually.
+    pub fn reactivate_coordinator(&mut self, coordinator_did: String) {
+        let codehash = self
+            .coordinators_by_did
+            .get(&coordinator_did)
+            .expect("Coordinator not found")
+            .codehash
+            .clone();
+        self.require_approved_codehash(&codehash);
+        let caller = env::predecessor_account_id();
+        let is_moderator = caller == self.admin || self.moderators.contains(&caller);
+        let entry = self.coordinators_by_did.get_mut(&coordinator_did).unwrap();
+        if entry.is_moderated() {
+            require!(
+                is_moderator,
+                "Only admin or moderator can reverse a moderation deactivation"
+            );
+        } else {
+            require!(
+                caller == entry.account_id || caller == self.admin,
+                "Only coordinator owner or admin can reactivate"
+            );
+        }
+        entry.reactivate(env::block_timestamp());
+        env::log_str(&format!("Reactivated coordinator: {}", coordinator_did));
+    }
+
+    /// Deactivate workers whose coordinator is deactivated or removed.
+    /// Permissionless. A coordinator that is merely stale or running a revoked
+    /// codehash does not qualify, since it can recover without re-registering
+    /// its workers. Examines at most `limit` workers (capped at 100) per call.
+    pub fn reap_orphaned_workers(
+        &mut self,
+        coordinator_did: String,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> ReapResult {
+        require!(
+            !self
+                .coordinators_by_did
+                .get(&coordinator_did)
+                .is_some_and(|c| c.is_active),
+            "Coordinator is still active"
+        );
+        let from = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(MAX_REAP_BATCH).min(MAX_REAP_BATCH);
+        let total = self.workers_by_did.len();
+        let end = from.saturating_add(limit).min(total);
+        let now = env::block_timestamp();
+        let mut deactivated = 0;
+
+        let dids: Vec<String> = self
+            .workers_by_did
+            .keys()
+            .skip(from as usize)
+            .take(end.saturating_sub(from) as usize)
+            .cloned()
+            .collect();
+        for did in dids {
+            let entry = self.workers_by_did.get_mut(&did).unwrap();
+            if entry.is_active && entry.coordinator_did == coordinator_did {
+                entry.deactivate(now, None);
+                deactivated += 1;
+                env::log_str(&format!("Reaped orphaned worker: {}", did));
+            }
+        }
+
+        ReapResult {
+            deactivated,
+            next_index: if end < total { Some(end) } else { None },
+        }
+    }
+
+    // ========== LIVENESS ==========
+
+    /// Record a liveness heartbeat for a worker or coordinator DID.
+    /// Only the record's own account can heartbeat.
+    pub fn heartbeat(&mut self, did: String) {
+        let caller = env::predecessor_account_id();
+        let now = env::block_timestamp();
+        if let Some(entry) = self.workers_by_did.get_mut(&did) {
+            require!(
+                caller == entry.account_id,
+                "Only the record's account can send heartbeats"
+            );
+            entry.last_seen = now;
+        } else if let Some(entry) = self.coordinators_by_did.get_mut(&did) {
+            require!(
+                caller == entry.account_id,
+                "Only the record's account can send heartbeats"
+            );
+            entry.last_seen = now;
+        } else {
+            env::panic_str("Agent not found");
+        }
+    }
+
+    /// Deactivate agents whose last heartbeat is older than `reap_after`.
+    /// Permissionless. Walks workers then coordinators as one index space,
+    /// examining at most `limit` records (capped at 100) per call.
+    pub fn reap_stale(&mut self, from_index: Option<u32>, limit: Option<u32>) -> ReapResult {
+        let from = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(MAX_REAP_BATCH).min(MAX_REAP_BATCH);
+        let worker_count = self.workers_by_did.len();
+        let total = worker_count + self.coordinators_by_did.len();
+        if from >= total {
+            return ReapResult {
+                deactivated: 0,
+                next_index: None,
+            };
+        }
+        let end = from.saturating_add(limit).min(total);
+        let now = env::block_timestamp();
+        let mut deactivated = 0;
+
+        if from < worker_count {
+            let dids: Vec<String> = self
+                .workers_by_did
+                .keys()
+                .skip(from as usize)
+                .take((end.min(worker_count) - from) as usize)
+                .cloned()
+                .collect();
+            for did in dids {
+                let entry = self.workers_by_did.get_mut(&did).unwrap();
+                if entry.is_active && now.saturating_sub(entry.last_seen) > self.reap_after {
+                    entry.deactivate(now, None);
+                    deactivated += 1;
+                    env::log_str(&format!("Reaped stale worker: {}", did));
+                }
+            }
+        }
+        if end > worker_count {
+            let start = from.max(worker_count) - worker_count;
+            let dids: Vec<String> = self
+                .coordinators_by_did
+                .keys()
+                .skip(start as usize)
+                .take((end - worker_count - start) as usize)
+                .cloned()
+                .collect();
+            for did in dids {
+                let entry = self.coordinators_by_did.get_mut(&did).unwrap();
+                if entry.is_active && now.saturating_sub(entry.last_seen) > self.reap_after {
+                    entry.deactivate(now, None);
+                    deactivated += 1;
+                    env::log_str(&format!("Reaped stale coordinator: {}", did));
+                }
+            }
+        }
+
+        ReapResult {
+            deactivated,
+            next_index: if end < total { Some(end) } else { None },
+        }
+    }
+
+    // ========== ROLES ==========
+
+    /// Propose a new admin (admin only). Takes effect once they call `accept_admin`.
+    pub fn propose_admin(&mut self, new_admin: AccountId) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can propose a new admin"
+        );
+        self.pending_admin = Some(new_admin.clone());
+        env::log_str(&format!("Proposed new admin: {}", new_admin));
+    }
+
+    /// Accept a pending admin transfer (pending admin only)
+    pub fn accept_admin(&mut self) {
+        let caller = env::predecessor_account_id();
+        require!(
+            self.pending_admin.as_ref() == Some(&caller),
+            "Only the pending admin can accept"
+        );
+        self.admin = caller.clone();
+        self.pending_admin = None;
+        env::log_str(&format!("Admin transferred to: {}", caller));
+    }
+
+    /// Cancel a pending admin transfer (admin only)
+    pub fn cancel_admin_transfer(&mut self) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can cancel an admin transfer"
+        );
+        self.pending_admin = None;
+        env::log_str("Cancelled pending admin transfer");
+    }
+
+    /// Grant the moderator role (admin only)
+    pub fn add_moderator(&mut self, account_id: AccountId) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can manage moderators"
+        );
+        self.moderators.insert(account_id.clone());
+        env::log_str(&format!("Added moderator: {}", account_id));
+    }
+
+    /// Revoke the moderator role (admin only)
+    pub fn remove_moderator(&mut self, account_id: AccountId) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can manage moderators"
+        );
+        self.moderators.remove(&account_id);
+        env::log_str(&format!("Removed moderator: {}", account_id));
+    }
+
     // ========== ADMIN ==========
 
     /// Set the minimum deposit required to register (admin only)
@@ -259,32 +788,147 @@ impl RegistryContract {
         env::log_str(&format!("Min deposit set to {}", self.min_deposit));
     }
 
+    /// Set the heartbeat staleness window and reap threshold in nanoseconds (admin only)
+    pub fn set_liveness_config(&mut self, staleness_window: u64, reap_after: u64) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can set liveness config"
+        );
+        require!(staleness_window > 0, "staleness_window must be > 0");
+        require!(
+            reap_after >= staleness_window,
+            "reap_after must be >= staleness_window"
+        );
+        self.staleness_window = staleness_window;
+        self.reap_after = reap_after;
+        env::log_str(&format!(
+            "Liveness config set: staleness_window={}ns, reap_after={}ns",
+            staleness_window, reap_after
+        ));
+    }
+
+    /// Allow or forbid `http://` endpoint URLs (admin only, for testnets)
+    pub fn set_allow_http(&mut self, allow: bool) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can set allow_http"
+        );
+        self.allow_http = allow;
+        env::log_str(&format!("allow_http set to {}", allow));
+    }
+
+    /// Allow a coordinator factory to call `register_coordinator_for` (admin only)
+    pub fn add_trusted_factory(&mut self, factory_id: AccountId) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can manage trusted factories"
+        );
+        self.trusted_factories.insert(factory_id.clone());
+        env::log_str(&format!("Added trusted factory: {}", factory_id));
+    }
+
+    /// Revoke a coordinator factory's `register_coordinator_for` access (admin only)
+    pub fn remove_trusted_factory(&mut self, factory_id: AccountId) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can manage trusted factories"
+        );
+        self.trusted_factories.remove(&factory_id);
+        env::log_str(&format!("Removed trusted factory: {}", factory_id));
+    }
+
+    /// Permanently delete a worker record (admin only)
+    pub fn remove_worker(&mut self, worker_did: String) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can remove records"
+        );
+        let record = self
+            .workers_by_did
+            .remove(&worker_did)
+            .expect("Worker not found");
+        self.worker_did_by_seq.remove(&record.worker_seq);
+        env::log_str(&format!("Removed worker: {}", worker_did));
+    }
+
+    /// Permanently delete a coordinator record (admin only).
+    /// Its workers become orphaned; see `reap_orphaned_workers`.
+    pub fn remove_coordinator(&mut self, coordinator_did: String) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can remove records"
+        );
+        require!(
+            self.coordinators_by_did.remove(&coordinator_did).is_some(),
+            "Coordinator not found"
+        );
+        env::log_str(&format!("Removed coordinator: {}", coordinator_did));
+    }
+
+    /// Approve a Phala TEE compose/measurement hash for registration (admin only)
+    pub fn approve_codehash(&mut self, codehash: String) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can approve codehashes"
+        );
+        self.approved_codehashes.insert(codehash.clone());
+        env::log_str(&format!("Approved codehash: {}", codehash));
+    }
+
+    /// Revoke an approved codehash (admin only).
+    /// Records registered with it show as inactive in all views.
+    pub fn remove_codehash(&mut self, codehash: String) {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only admin can remove codehashes"
+        );
+        self.approved_codehashes.remove(&codehash);
+        env::log_str(&format!("Removed codehash: {}", codehash));
+    }
+
     // ========== VIEW FUNCTIONS ==========
 
     /// Get all active workers belonging to a specific coordinator
     pub fn get_workers_for_coordinator(&self, coordinator_did: String) -> Vec<WorkerRecord> {
         self.workers_by_did
             .values()
-            .filter(|w| w.is_active && w.coordinator_did == coordinator_did)
+            .filter(|w| self.is_worker_live(w) && w.coordinator_did == coordinator_did)
             .cloned()
             .collect()
     }
 
     /// Look up a single worker by DID
     pub fn get_worker_by_did(&self, worker_did: String) -> Option<WorkerRecord> {
-        self.workers_by_did.get(&worker_did).cloned()
+        self.workers_by_did
+            .get(&worker_did)
+            .map(|w| self.worker_view(w))
     }
 
     /// Look up a single coordinator by DID
     pub fn get_coordinator_by_did(&self, coordinator_did: String) -> Option<CoordinatorRecord> {
-        self.coordinators_by_did.get(&coordinator_did).cloned()
+        self.coordinators_by_did
+            .get(&coordinator_did)
+            .map(|c| self.coordinator_view(c))
+    }
+
+    /// Look up a single worker by its numeric `worker_seq`
+    pub fn get_worker_by_seq(&self, worker_seq: u64) -> Option<WorkerRecord> {
+        self.worker_did_by_seq
+            .get(&worker_seq)
+            .and_then(|did| self.workers_by_did.get(did))
+            .map(|w| self.worker_view(w))
+    }
+
+    /// Get the numeric `worker_seq` assigned to a worker DID
+    pub fn get_worker_seq(&self, worker_did: String) -> Option<u64> {
+        self.workers_by_did.get(&worker_did).map(|w| w.worker_seq)
     }
 
     /// List all active workers
     pub fn list_active_workers(&self) -> Vec<WorkerRecord> {
         self.workers_by_did
             .values()
-            .filter(|w| w.is_active)
+            .filter(|w| self.is_worker_live(w))
             .cloned()
             .collect()
     }
@@ -293,7 +937,7 @@ impl RegistryContract {
     pub fn list_active_coordinators(&self) -> Vec<CoordinatorRecord> {
         self.coordinators_by_did
             .values()
-            .filter(|c| c.is_active)
+            .filter(|c| self.is_coordinator_live(c))
             .cloned()
             .collect()
     }
@@ -303,12 +947,12 @@ impl RegistryContract {
         let active_coords = self
             .coordinators_by_did
             .values()
-            .filter(|c| c.is_active)
+            .filter(|c| self.is_coordinator_live(c))
             .count();
         let active_workers = self
             .workers_by_did
             .values()
-            .filter(|w| w.is_active)
+            .filter(|w| self.is_worker_live(w))
             .count();
         serde_json::json!({
             "total_coordinators": self.coordinators_by_did.len(),
@@ -323,42 +967,287 @@ impl RegistryContract {
         self.admin.clone()
     }
 
+    /// Get the account proposed as next admin, if any
+    pub fn get_pending_admin(&self) -> Option<AccountId> {
+        self.pending_admin.clone()
+    }
+
+    /// List all moderators
+    pub fn get_moderators(&self) -> Vec<AccountId> {
+        self.moderators.iter().cloned().collect()
+    }
+
+    /// Get all current role assignments
+    pub fn get_roles(&self) -> Roles {
+        Roles {
+            admin: self.admin.clone(),
+            pending_admin: self.pending_admin.clone(),
+            moderators: self.get_moderators(),
+        }
+    }
+
+    /// List coordinator factories trusted to register on behalf of owners
+    pub fn get_trusted_factories(&self) -> Vec<AccountId> {
+        self.trusted_factories.iter().cloned().collect()
+    }
+
     /// Get the current minimum deposit (as yoctoNEAR string)
     pub fn get_min_deposit(&self) -> String {
         self.min_deposit.as_yoctonear().to_string()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
-    use near_sdk::testing_env;
+    /// Get the liveness config as `{ staleness_window, reap_after }` (nanoseconds)
+    pub fn get_liveness_config(&self) -> serde_json::Value {
+        serde_json::json!({
+            "staleness_window": self.staleness_window,
+            "reap_after": self.reap_after,
+        })
+    }
 
-    const COORD_DID: &str = "did:key:z6MkCoordinator1";
-    const WORKER_DID: &str = "did:key:z6MkWorker1";
-    const WORKER_DID_2: &str = "did:key:z6MkWorker2";
+    /// Whether `http://` endpoint URLs are accepted
+    pub fn get_allow_http(&self) -> bool {
+        self.allow_http
+    }
 
-    fn get_context(predecessor: AccountId) -> VMContextBuilder {
-        let mut builder = VMContextBuilder::new();
-        builder
-            .predecessor_account_id(predecessor.clone())
-            .signer_account_id(predecessor)
-            .attached_deposit(NearToken::from_near(1));
-        builder
+    /// Check whether a TEE codehash is currently approved
+    pub fn is_codehash_approved(&self, codehash: String) -> bool {
+        self.approved_codehashes.contains(&codehash)
     }
 
-    fn setup_contract() -> RegistryContract {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
-        RegistryContract::new(accounts(0))
+    /// List all approved TEE codehashes
+    pub fn get_approved_codehashes(&self) -> Vec<String> {
+        self.approved_codehashes.iter().cloned().collect()
     }
 
-    fn register_test_coordinator(contract: &mut RegistryContract) -> CoordinatorRecord {
-        contract.register_coordinator(
-            COORD_DID.to_string(),
-            "https://coord.example.com".to_string(),
-            "cvm-coord-1".to_string(),
+    // ========== INTERNAL ==========
+
+    fn require_approved_codehash(&self, codehash: &str) {
+        require!(
+            self.approved_codehashes.contains(codehash),
+            "Codehash not approved. Admin must approve_codehash first."
+        );
+    }
+
+    /// Require an https (or, when allowed, http) URL with a well-formed host
+    fn validate_endpoint_url(&self, url: &str) {
+        require!(
+            url.len() <= MAX_ENDPOINT_URL_LEN,
+            format!("Endpoint URL must be at most {} characters", MAX_ENDPOINT_URL_LEN)
+        );
+        let rest = if let Some(rest) = url.strip_prefix("https://") {
+            rest
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            require!(self.allow_http, "Endpoint URL must use https");
+            rest
+        } else {
+            env::panic_str("Endpoint URL must use https");
+        };
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+        require!(
+            is_valid_authority(authority),
+            format!("Endpoint URL has an invalid host: {}", url)
+        );
+    }
+
+    fn validate_endpoints(&self, endpoints: &[Endpoint]) {
+        for (i, endpoint) in endpoints.iter().enumerate() {
+            self.validate_endpoint_url(&endpoint.url);
+            require!(
+                !endpoints[..i].iter().any(|e| e.kind == endpoint.kind),
+                format!("Duplicate endpoint kind: {:?}", endpoint.kind)
+            );
+        }
+        require!(
+            endpoints.iter().any(|e| e.kind == EndpointKind::Api),
+            "Endpoints must include an Api endpoint"
+        );
+    }
+
+    fn is_fresh(&self, last_seen: u64) -> bool {
+        env::block_timestamp().saturating_sub(last_seen) <= self.staleness_window
+    }
+
+    /// A worker is live while active, recently seen, its codehash is still
+    /// approved and its coordinator is live (otherwise it is orphaned)
+    fn is_worker_live(&self, worker: &WorkerRecord) -> bool {
+        worker.is_active
+            && self.is_fresh(worker.last_seen)
+            && self.approved_codehashes.contains(&worker.codehash)
+            && self
+                .coordinators_by_did
+                .get(&worker.coordinator_did)
+                .is_some_and(|c| self.is_coordinator_live(c))
+    }
+
+    /// A coordinator is live while active, recently seen and its codehash is still approved
+    fn is_coordinator_live(&self, coordinator: &CoordinatorRecord) -> bool {
+        coordinator.is_active
+            && self.is_fresh(coordinator.last_seen)
+            && self.approved_codehashes.contains(&coordinator.codehash)
+    }
+
+    /// Worker as returned by views: `is_active` reflects staleness, codehash
+    /// revocation and orphaning
+    fn worker_view(&self, worker: &WorkerRecord) -> WorkerRecord {
+        WorkerRecord {
+            is_active: self.is_worker_live(worker),
+            ..worker.clone()
+        }
+    }
+
+    /// Coordinator as returned by views: `is_active` reflects staleness and codehash revocation
+    fn coordinator_view(&self, coordinator: &CoordinatorRecord) -> CoordinatorRecord {
+        CoordinatorRecord {
+            is_active: self.is_coordinator_live(coordinator),
+            ..coordinator.clone()
+        }
+    }
+}
+
+impl WorkerRecord {
+    /// Keeps an existing moderation stamp so the owner cannot overwrite it
+    fn deactivate(&mut self, now: u64, by: Option<AccountId>) {
+        if self.is_moderated() {
+            return;
+        }
+        self.is_active = false;
+        self.updated_at = now;
+        self.deactivated_at = Some(now);
+        self.deactivated_by = by;
+    }
+
+    fn reactivate(&mut self, now: u64) {
+        self.is_active = true;
+        self.updated_at = now;
+        self.last_seen = now;
+        self.deactivated_at = None;
+        self.deactivated_by = None;
+    }
+
+    /// Deactivated by someone other than the record's own account
+    fn is_moderated(&self) -> bool {
+        !self.is_active
+            && self
+                .deactivated_by
+                .as_ref()
+                .is_some_and(|by| *by != self.account_id)
+    }
+}
+
+impl CoordinatorRecord {
+    /// Keeps an existing moderation stamp so the owner cannot overwrite it
+    fn deactivate(&mut self, now: u64, by: Option<AccountId>) {
+        if self.is_moderated() {
+            return;
+        }
+        self.is_active = false;
+        self.updated_at = now;
+        self.deactivated_at = Some(now);
+        self.deactivated_by = by;
+    }
+
+    fn reactivate(&mut self, now: u64) {
+        self.is_active = true;
+        self.updated_at = now;
+        self.last_seen = now;
+        self.deactivated_at = None;
+        self.deactivated_by = None;
+    }
+
+    /// Deactivated by someone other than the record's own account
+    fn is_moderated(&self) -> bool {
+        !self.is_active
+            && self
+                .deactivated_by
+                .as_ref()
+                .is_some_and(|by| *by != self.account_id)
+    }
+}
+
+/// Insert or replace the `Api` entry of an endpoint list
+fn set_api_endpoint(endpoints: &mut Vec<Endpoint>, url: &str) {
+    match endpoints.iter_mut().find(|e| e.kind == EndpointKind::Api) {
+        Some(api) => api.url = url.to_string(),
+        None => endpoints.insert(
+            0,
+            Endpoint {
+                kind: EndpointKind::Api,
+                url: url.to_string(),
+            },
+        ),
+    }
+}
+
+/// URL of the `Api` entry of an already validated endpoint list
+fn api_endpoint_url(endpoints: &[Endpoint]) -> String {
+    endpoints
+        .iter()
+        .find(|e| e.kind == EndpointKind::Api)
+        .map(|e| e.url.clone())
+        .unwrap_or_default()
+}
+
+/// `host[:port]` with DNS-style labels; rejects userinfo, IPv6 literals and empty hosts
+fn is_valid_authority(authority: &str) -> bool {
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (authority, None),
+    };
+    if let Some(port) = port {
+        if port.is_empty() || port.len() > 5 || !port.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+    }
+    !host.is_empty()
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    const COORD_DID: &str = "did:key:z6MkCoordinator1";
+    const WORKER_DID: &str = "did:key:z6MkWorker1";
+    const WORKER_DID_2: &str = "did:key:z6MkWorker2";
+    const CODEHASH: &str = "7173eea7b2fb1c7f76ad3b88d65fb23f50cbb465d42eeacd726623da643d666c";
+
+    fn get_context(predecessor: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .predecessor_account_id(predecessor.clone())
+            .signer_account_id(predecessor)
+            .attached_deposit(NearToken::from_near(1));
+        builder
+    }
+
+    fn setup_contract() -> RegistryContract {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = RegistryContract::new(accounts(0));
+        contract.approve_codehash(CODEHASH.to_string());
+        contract
+    }
+
+    fn set_block_timestamp(predecessor: AccountId, timestamp: u64) {
+        let mut builder = get_context(predecessor);
+        builder.block_timestamp(timestamp);
+        testing_env!(builder.build());
+    }
+
+    fn register_test_coordinator(contract: &mut RegistryContract) -> CoordinatorRecord {
+        contract.register_coordinator(
+            COORD_DID.to_string(),
+            "https://coord.example.com".to_string(),
+            "cvm-coord-1".to_string(),
+            CODEHASH.to_string(),
             1,
             5,
         )
@@ -370,6 +1259,7 @@ mod tests {
             WORKER_DID.to_string(),
             "https://worker1.example.com".to_string(),
             "cvm-worker-1".to_string(),
+            CODEHASH.to_string(),
         )
     }
 
@@ -414,10 +1304,12 @@ mod tests {
             .attached_deposit(NearToken::from_millinear(1)); // 0.001 NEAR < 0.1 min
         testing_env!(builder.build());
         let mut contract = RegistryContract::new(accounts(0));
+        contract.approve_codehash(CODEHASH.to_string());
         contract.register_coordinator(
             COORD_DID.to_string(),
             "https://coord.example.com".to_string(),
             "cvm-1".to_string(),
+            CODEHASH.to_string(),
             1,
             3,
         );
@@ -431,6 +1323,7 @@ mod tests {
             "not-a-did".to_string(),
             "https://coord.example.com".to_string(),
             "cvm-1".to_string(),
+            CODEHASH.to_string(),
             1,
             3,
         );
@@ -446,6 +1339,7 @@ mod tests {
             COORD_DID.to_string(),
             "https://new-coord.example.com".to_string(),
             "cvm-coord-2".to_string(),
+            CODEHASH.to_string(),
             2,
             10,
         );
@@ -455,6 +1349,48 @@ mod tests {
         assert_eq!(contract.list_active_coordinators().len(), 1);
     }
 
+    #[test]
+    fn test_register_coordinator_for_owner_via_factory() {
+        let mut contract = setup_contract();
+        contract.add_trusted_factory(accounts(3));
+        assert_eq!(contract.get_trusted_factories(), vec![accounts(3)]);
+
+        testing_env!(get_context(accounts(3)).build());
+        let registration = CoordinatorRegistration {
+            coordinator_did: COORD_DID.to_string(),
+            endpoint_url: "https://coord.example.com".to_string(),
+            cvm_id: "cvm-coord-1".to_string(),
+            codehash: CODEHASH.to_string(),
+            min_workers: 1,
+            max_workers: 5,
+        };
+        let record = contract.register_coordinator_for(accounts(1), registration);
+        assert_eq!(record.account_id, accounts(1));
+
+        // The owner, not the factory, controls the record
+        testing_env!(get_context(accounts(1)).build());
+        contract.update_coordinator_endpoint(
+            COORD_DID.to_string(),
+            "https://coord-v2.example.com".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only a trusted factory can register on behalf of an owner")]
+    fn test_register_coordinator_for_untrusted() {
+        let mut contract = setup_contract();
+        testing_env!(get_context(accounts(3)).build());
+        let registration = CoordinatorRegistration {
+            coordinator_did: COORD_DID.to_string(),
+            endpoint_url: "https://coord.example.com".to_string(),
+            cvm_id: "cvm-coord-1".to_string(),
+            codehash: CODEHASH.to_string(),
+            min_workers: 1,
+            max_workers: 5,
+        };
+        contract.register_coordinator_for(accounts(1), registration);
+    }
+
     // ========== WORKER REGISTRATION ==========
 
     #[test]
@@ -482,10 +1418,12 @@ mod tests {
             .attached_deposit(NearToken::from_near(1));
         testing_env!(builder.build());
         let mut contract = RegistryContract::new(accounts(0));
+        contract.approve_codehash(CODEHASH.to_string());
         contract.register_coordinator(
             COORD_DID.to_string(),
             "https://coord.example.com".to_string(),
             "cvm-1".to_string(),
+            CODEHASH.to_string(),
             1,
             3,
         );
@@ -503,6 +1441,7 @@ mod tests {
             WORKER_DID.to_string(),
             "https://worker.example.com".to_string(),
             "cvm-w-1".to_string(),
+            CODEHASH.to_string(),
         );
     }
 
@@ -515,6 +1454,7 @@ mod tests {
             WORKER_DID.to_string(),
             "https://worker.example.com".to_string(),
             "cvm-w-1".to_string(),
+            CODEHASH.to_string(),
         );
     }
 
@@ -529,6 +1469,7 @@ mod tests {
             WORKER_DID.to_string(),
             "https://worker.example.com".to_string(),
             "cvm-w-1".to_string(),
+            CODEHASH.to_string(),
         );
     }
 
@@ -542,6 +1483,7 @@ mod tests {
             "not-a-did".to_string(),
             "https://worker.example.com".to_string(),
             "cvm-w-1".to_string(),
+            CODEHASH.to_string(),
         );
     }
 
@@ -557,11 +1499,59 @@ mod tests {
             WORKER_DID.to_string(),
             "https://new-worker.example.com".to_string(),
             "cvm-worker-updated".to_string(),
+            CODEHASH.to_string(),
         );
         assert_eq!(updated.endpoint_url, "https://new-worker.example.com");
         assert_eq!(contract.list_active_workers().len(), 1);
     }
 
+    // ========== WORKER SEQ ==========
+
+    #[test]
+    fn test_worker_seq_assigned_and_indexed() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        let first = register_test_worker(&mut contract);
+        let second = contract.register_worker(
+            COORD_DID.to_string(),
+            WORKER_DID_2.to_string(),
+            "https://worker2.example.com".to_string(),
+            "cvm-worker-2".to_string(),
+            CODEHASH.to_string(),
+        );
+        assert_eq!(first.worker_seq, 1);
+        assert_eq!(second.worker_seq, 2);
+
+        assert_eq!(contract.get_worker_seq(WORKER_DID_2.to_string()), Some(2));
+        assert_eq!(
+            contract.get_worker_by_seq(1).unwrap().worker_did,
+            WORKER_DID
+        );
+        assert!(contract.get_worker_by_seq(3).is_none());
+    }
+
+    #[test]
+    fn test_worker_seq_kept_across_upsert() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+        let updated = register_test_worker(&mut contract);
+        assert_eq!(updated.worker_seq, 1);
+        assert_eq!(contract.next_worker_seq, 1);
+    }
+
+    #[test]
+    fn test_worker_seq_not_reused_after_remove() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+        contract.remove_worker(WORKER_DID.to_string());
+        assert!(contract.get_worker_by_seq(1).is_none());
+
+        let again = register_test_worker(&mut contract);
+        assert_eq!(again.worker_seq, 2);
+    }
+
     // ========== VIEW: get_workers_for_coordinator ==========
 
     #[test]
@@ -576,6 +1566,7 @@ mod tests {
             WORKER_DID_2.to_string(),
             "https://worker2.example.com".to_string(),
             "cvm-worker-2".to_string(),
+            CODEHASH.to_string(),
         );
 
         let workers = contract.get_workers_for_coordinator(COORD_DID.to_string());
@@ -635,7 +1626,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Only worker owner or admin can deactivate")]
+    #[should_panic(expected = "Only worker owner, admin or moderator can deactivate")]
     fn test_deactivate_worker_unauthorized() {
         let mut contract = setup_contract();
         register_test_coordinator(&mut contract);
@@ -697,6 +1688,188 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_update_coordinator_endpoint() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+
+        contract.update_coordinator_endpoint(
+            COORD_DID.to_string(),
+            "https://coord-v2.example.com:8443/api".to_string(),
+        );
+
+        let coord = contract.get_coordinator_by_did(COORD_DID.to_string()).unwrap();
+        assert_eq!(coord.endpoint_url, "https://coord-v2.example.com:8443/api");
+        assert_eq!(coord.endpoints.len(), 1);
+        assert_eq!(coord.endpoints[0].url, coord.endpoint_url);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only coordinator owner or admin can update endpoint")]
+    fn test_update_coordinator_endpoint_unauthorized() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.update_coordinator_endpoint(
+            COORD_DID.to_string(),
+            "https://hacker.example.com".to_string(),
+        );
+    }
+
+    // ========== ENDPOINT VALIDATION ==========
+
+    #[test]
+    #[should_panic(expected = "Endpoint URL must use https")]
+    fn test_register_worker_empty_endpoint() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        contract.register_worker(
+            COORD_DID.to_string(),
+            WORKER_DID.to_string(),
+            "".to_string(),
+            "cvm-w-1".to_string(),
+            CODEHASH.to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Endpoint URL must use https")]
+    fn test_http_endpoint_rejected_by_default() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+        contract.update_worker_endpoint(
+            WORKER_DID.to_string(),
+            "http://worker.example.com".to_string(),
+        );
+    }
+
+    #[test]
+    fn test_http_endpoint_allowed_with_toggle() {
+        let mut contract = setup_contract();
+        contract.set_allow_http(true);
+        assert!(contract.get_allow_http());
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+        contract.update_worker_endpoint(
+            WORKER_DID.to_string(),
+            "http://localhost:3001".to_string(),
+        );
+        let worker = contract.get_worker_by_did(WORKER_DID.to_string()).unwrap();
+        assert_eq!(worker.endpoint_url, "http://localhost:3001");
+    }
+
+    #[test]
+    #[should_panic(expected = "Endpoint URL has an invalid host")]
+    fn test_endpoint_with_userinfo_rejected() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        contract.update_coordinator_endpoint(
+            COORD_DID.to_string(),
+            "https://user@evil.example.com".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Endpoint URL must be at most 256 characters")]
+    fn test_endpoint_too_long_rejected() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        contract.update_coordinator_endpoint(
+            COORD_DID.to_string(),
+            format!("https://example.com/{}", "a".repeat(256)),
+        );
+    }
+
+    #[test]
+    fn test_is_valid_authority() {
+        assert!(is_valid_authority("example.com"));
+        assert!(is_valid_authority("worker-1.example.com:8080"));
+        assert!(!is_valid_authority(""));
+        assert!(!is_valid_authority("example..com"));
+        assert!(!is_valid_authority("-bad.example.com"));
+        assert!(!is_valid_authority("example.com:"));
+        assert!(!is_valid_authority("example.com:80a"));
+        assert!(!is_valid_authority("exa mple.com"));
+    }
+
+    // ========== TYPED ENDPOINTS ==========
+
+    #[test]
+    fn test_set_worker_endpoints() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        contract.set_worker_endpoints(
+            WORKER_DID.to_string(),
+            vec![
+                Endpoint {
+                    kind: EndpointKind::Stream,
+                    url: "https://worker1.example.com/events".to_string(),
+                },
+                Endpoint {
+                    kind: EndpointKind::Api,
+                    url: "https://api.worker1.example.com".to_string(),
+                },
+                Endpoint {
+                    kind: EndpointKind::Health,
+                    url: "https://worker1.example.com/health".to_string(),
+                },
+            ],
+        );
+
+        let worker = contract.get_worker_by_did(WORKER_DID.to_string()).unwrap();
+        assert_eq!(worker.endpoint_url, "https://api.worker1.example.com");
+        assert_eq!(worker.endpoints.len(), 3);
+
+        // Re-registering replaces only the Api endpoint
+        let updated = contract.register_worker(
+            COORD_DID.to_string(),
+            WORKER_DID.to_string(),
+            "https://new-worker.example.com".to_string(),
+            "cvm-worker-1".to_string(),
+            CODEHASH.to_string(),
+        );
+        assert_eq!(updated.endpoints.len(), 3);
+        assert_eq!(updated.endpoints[1].url, "https://new-worker.example.com");
+    }
+
+    #[test]
+    #[should_panic(expected = "Endpoints must include an Api endpoint")]
+    fn test_set_endpoints_requires_api() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        contract.set_coordinator_endpoints(
+            COORD_DID.to_string(),
+            vec![Endpoint {
+                kind: EndpointKind::Health,
+                url: "https://coord.example.com/health".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate endpoint kind: Api")]
+    fn test_set_endpoints_rejects_duplicate_kind() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        contract.set_coordinator_endpoints(
+            COORD_DID.to_string(),
+            vec![
+                Endpoint {
+                    kind: EndpointKind::Api,
+                    url: "https://a.example.com".to_string(),
+                },
+                Endpoint {
+                    kind: EndpointKind::Api,
+                    url: "https://b.example.com".to_string(),
+                },
+            ],
+        );
+    }
+
     // ========== DEACTIVATE COORDINATOR ==========
 
     #[test]
@@ -713,6 +1886,251 @@ mod tests {
         assert!(!coord.unwrap().is_active);
     }
 
+    // ========== LIFECYCLE ==========
+
+    #[test]
+    fn test_deactivate_and_reactivate_worker_timestamps() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        set_block_timestamp(accounts(0), 10);
+        contract.deactivate_worker(WORKER_DID.to_string());
+        let worker = contract.get_worker_by_did(WORKER_DID.to_string()).unwrap();
+        assert_eq!(worker.deactivated_at, Some(10));
+        assert_eq!(worker.updated_at, 10);
+
+        set_block_timestamp(accounts(0), 20);
+        contract.reactivate_worker(WORKER_DID.to_string());
+        let worker = contract.get_worker_by_did(WORKER_DID.to_string()).unwrap();
+        assert!(worker.is_active);
+        assert_eq!(worker.deactivated_at, None);
+        assert_eq!(worker.updated_at, 20);
+        assert_eq!(worker.registered_at, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinator is not active")]
+    fn test_reactivate_worker_inactive_coordinator() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+        contract.deactivate_worker(WORKER_DID.to_string());
+        contract.deactivate_coordinator(COORD_DID.to_string());
+        contract.reactivate_worker(WORKER_DID.to_string());
+    }
+
+    #[test]
+    fn test_deactivate_coordinator_orphans_workers() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        contract.deactivate_coordinator(COORD_DID.to_string());
+        assert_eq!(contract.list_active_workers().len(), 0);
+        assert!(!contract.get_worker_by_did(WORKER_DID.to_string()).unwrap().is_active);
+        // Stored flag is untouched until reaped
+        assert!(contract.workers_by_did.get(WORKER_DID).unwrap().is_active);
+
+        let result = contract.reap_orphaned_workers(COORD_DID.to_string(), None, None);
+        assert_eq!(result.deactivated, 1);
+        assert_eq!(result.next_index, None);
+        assert!(!contract.workers_by_did.get(WORKER_DID).unwrap().is_active);
+
+        // Reactivating the coordinator does not revive reaped workers
+        contract.reactivate_coordinator(COORD_DID.to_string());
+        assert_eq!(contract.list_active_coordinators().len(), 1);
+        assert_eq!(contract.list_active_workers().len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinator is still active")]
+    fn test_reap_orphaned_workers_active_coordinator() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+        contract.reap_orphaned_workers(COORD_DID.to_string(), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinator is still active")]
+    fn test_reap_orphaned_workers_stale_coordinator() {
+        let mut contract = setup_contract();
+        contract.set_liveness_config(100, 1_000);
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        // A silent coordinator is not live, but its workers are not orphaned
+        set_block_timestamp(accounts(2), 500);
+        assert!(!contract.get_coordinator_by_did(COORD_DID.to_string()).unwrap().is_active);
+        contract.reap_orphaned_workers(COORD_DID.to_string(), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Codehash not approved")]
+    fn test_reactivate_coordinator_revoked_codehash() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+
+        contract.deactivate_coordinator(COORD_DID.to_string());
+        contract.remove_codehash(CODEHASH.to_string());
+        contract.reactivate_coordinator(COORD_DID.to_string());
+    }
+
+    #[test]
+    fn test_remove_records() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        contract.remove_worker(WORKER_DID.to_string());
+        assert!(contract.get_worker_by_did(WORKER_DID.to_string()).is_none());
+
+        contract.remove_coordinator(COORD_DID.to_string());
+        assert!(contract.get_coordinator_by_did(COORD_DID.to_string()).is_none());
+        assert_eq!(contract.get_stats()["total_coordinators"], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only admin can remove records")]
+    fn test_remove_worker_unauthorized() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.remove_worker(WORKER_DID.to_string());
+    }
+
+    // ========== ROLES ==========
+
+    #[test]
+    fn test_two_step_admin_transfer() {
+        let mut contract = setup_contract();
+        contract.propose_admin(accounts(1));
+        assert_eq!(contract.get_pending_admin(), Some(accounts(1)));
+        // Admin unchanged until accepted
+        assert_eq!(contract.get_admin(), accounts(0));
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.accept_admin();
+        assert_eq!(contract.get_admin(), accounts(1));
+        assert_eq!(contract.get_pending_admin(), None);
+
+        // New admin can act
+        contract.set_min_deposit("1".to_string());
+        assert_eq!(contract.get_min_deposit(), "1");
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the pending admin can accept")]
+    fn test_accept_admin_wrong_account() {
+        let mut contract = setup_contract();
+        contract.propose_admin(accounts(1));
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.accept_admin();
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the pending admin can accept")]
+    fn test_cancel_admin_transfer() {
+        let mut contract = setup_contract();
+        contract.propose_admin(accounts(1));
+        contract.cancel_admin_transfer();
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.accept_admin();
+    }
+
+    #[test]
+    fn test_moderator_can_deactivate_records() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+        contract.add_moderator(accounts(2));
+
+        let roles = contract.get_roles();
+        assert_eq!(roles.admin, accounts(0));
+        assert_eq!(roles.moderators, vec![accounts(2)]);
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.deactivate_worker(WORKER_DID.to_string());
+        contract.deactivate_coordinator(COORD_DID.to_string());
+        assert_eq!(contract.list_active_workers().len(), 0);
+        assert_eq!(contract.list_active_coordinators().len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only admin can set min deposit")]
+    fn test_moderator_cannot_set_min_deposit() {
+        let mut contract = setup_contract();
+        contract.add_moderator(accounts(2));
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.set_min_deposit("1".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only worker owner, admin or moderator can deactivate")]
+    fn test_removed_moderator_cannot_deactivate() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+        contract.add_moderator(accounts(2));
+        contract.remove_moderator(accounts(2));
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.deactivate_worker(WORKER_DID.to_string());
+    }
+
+    /// Coordinator owned by admin, worker owned by bob, worker deactivated by moderator charlie
+    fn moderated_worker_contract() -> RegistryContract {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        contract.add_moderator(accounts(2));
+        testing_env!(get_context(accounts(1)).build());
+        register_test_worker(&mut contract);
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.deactivate_worker(WORKER_DID.to_string());
+        contract
+    }
+
+    #[test]
+    #[should_panic(expected = "Only admin or moderator can reverse a moderation deactivation")]
+    fn test_owner_cannot_reverse_moderation() {
+        let mut contract = moderated_worker_contract();
+        assert_eq!(
+            contract.workers_by_did.get(WORKER_DID).unwrap().deactivated_by,
+            Some(accounts(2))
+        );
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.reactivate_worker(WORKER_DID.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Record was deactivated by moderation")]
+    fn test_owner_cannot_reregister_moderated_record() {
+        let mut contract = moderated_worker_contract();
+
+        // Deactivating again as owner keeps the moderation stamp
+        testing_env!(get_context(accounts(1)).build());
+        contract.deactivate_worker(WORKER_DID.to_string());
+        register_test_worker(&mut contract);
+    }
+
+    #[test]
+    fn test_moderator_can_reverse_moderation() {
+        let mut contract = moderated_worker_contract();
+
+        contract.reactivate_worker(WORKER_DID.to_string());
+        let worker = contract.workers_by_did.get(WORKER_DID).unwrap();
+        assert!(worker.is_active);
+        assert_eq!(worker.deactivated_by, None);
+    }
+
     // ========== ADMIN ==========
 
     #[test]
@@ -737,6 +2155,186 @@ mod tests {
         contract.set_min_deposit("1000000000000000000000000".to_string());
     }
 
+    // ========== CODEHASH ALLOWLIST ==========
+
+    #[test]
+    fn test_register_stores_codehash() {
+        let mut contract = setup_contract();
+        let coord = register_test_coordinator(&mut contract);
+        let worker = register_test_worker(&mut contract);
+        assert_eq!(coord.codehash, CODEHASH);
+        assert_eq!(worker.codehash, CODEHASH);
+    }
+
+    #[test]
+    #[should_panic(expected = "Codehash not approved")]
+    fn test_register_coordinator_unapproved_codehash() {
+        let mut contract = setup_contract();
+        contract.register_coordinator(
+            COORD_DID.to_string(),
+            "https://coord.example.com".to_string(),
+            "cvm-1".to_string(),
+            "unknown_codehash".to_string(),
+            1,
+            3,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Codehash not approved")]
+    fn test_register_worker_unapproved_codehash() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        contract.register_worker(
+            COORD_DID.to_string(),
+            WORKER_DID.to_string(),
+            "https://worker.example.com".to_string(),
+            "cvm-w-1".to_string(),
+            "unknown_codehash".to_string(),
+        );
+    }
+
+    #[test]
+    fn test_remove_codehash_deactivates_records_in_views() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        contract.remove_codehash(CODEHASH.to_string());
+        assert!(!contract.is_codehash_approved(CODEHASH.to_string()));
+
+        assert_eq!(contract.list_active_coordinators().len(), 0);
+        assert_eq!(contract.list_active_workers().len(), 0);
+        assert_eq!(contract.get_workers_for_coordinator(COORD_DID.to_string()).len(), 0);
+        assert!(!contract.get_worker_by_did(WORKER_DID.to_string()).unwrap().is_active);
+        assert!(!contract.get_coordinator_by_did(COORD_DID.to_string()).unwrap().is_active);
+        assert_eq!(contract.get_stats()["active_workers"], 0);
+
+        // Re-approving restores them
+        contract.approve_codehash(CODEHASH.to_string());
+        assert_eq!(contract.list_active_workers().len(), 1);
+        assert_eq!(contract.list_active_coordinators().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only admin can approve codehashes")]
+    fn test_approve_codehash_unauthorized() {
+        let mut contract = setup_contract();
+
+        let mut builder = VMContextBuilder::new();
+        builder
+            .predecessor_account_id(accounts(1))
+            .signer_account_id(accounts(1));
+        testing_env!(builder.build());
+
+        contract.approve_codehash("other_codehash".to_string());
+    }
+
+    // ========== LIVENESS ==========
+
+    #[test]
+    fn test_heartbeat_updates_last_seen() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        set_block_timestamp(accounts(0), 1_000);
+        contract.heartbeat(WORKER_DID.to_string());
+        contract.heartbeat(COORD_DID.to_string());
+
+        let worker = contract.get_worker_by_did(WORKER_DID.to_string()).unwrap();
+        let coord = contract.get_coordinator_by_did(COORD_DID.to_string()).unwrap();
+        assert_eq!(worker.last_seen, 1_000);
+        assert_eq!(coord.last_seen, 1_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the record's account can send heartbeats")]
+    fn test_heartbeat_unauthorized() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        set_block_timestamp(accounts(1), 1_000);
+        contract.heartbeat(WORKER_DID.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Agent not found")]
+    fn test_heartbeat_unknown_did() {
+        let mut contract = setup_contract();
+        contract.heartbeat("did:key:z6MkNonexistent".to_string());
+    }
+
+    #[test]
+    fn test_stale_agents_hidden_from_views() {
+        let mut contract = setup_contract();
+        contract.set_liveness_config(100, 1_000);
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        set_block_timestamp(accounts(0), 101);
+        assert_eq!(contract.list_active_workers().len(), 0);
+        assert_eq!(contract.list_active_coordinators().len(), 0);
+        assert!(!contract.get_worker_by_did(WORKER_DID.to_string()).unwrap().is_active);
+
+        contract.heartbeat(WORKER_DID.to_string());
+        contract.heartbeat(COORD_DID.to_string());
+        assert_eq!(contract.list_active_workers().len(), 1);
+        assert_eq!(contract.list_active_coordinators().len(), 1);
+    }
+
+    #[test]
+    fn test_reap_stale_in_batches() {
+        let mut contract = setup_contract();
+        contract.set_liveness_config(100, 1_000);
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+        contract.register_worker(
+            COORD_DID.to_string(),
+            WORKER_DID_2.to_string(),
+            "https://worker2.example.com".to_string(),
+            "cvm-worker-2".to_string(),
+            CODEHASH.to_string(),
+        );
+
+        // Worker 2 keeps heartbeating, the others go silent
+        set_block_timestamp(accounts(0), 900);
+        contract.heartbeat(WORKER_DID_2.to_string());
+
+        // Anyone can reap
+        set_block_timestamp(accounts(2), 1_500);
+        let first = contract.reap_stale(None, Some(2));
+        assert_eq!(first.deactivated, 1);
+        assert_eq!(first.next_index, Some(2));
+
+        let second = contract.reap_stale(first.next_index, Some(2));
+        assert_eq!(second.deactivated, 1);
+        assert_eq!(second.next_index, None);
+
+        assert!(!contract.workers_by_did.get(WORKER_DID).unwrap().is_active);
+        assert!(contract.workers_by_did.get(WORKER_DID_2).unwrap().is_active);
+        assert!(!contract.coordinators_by_did.get(COORD_DID).unwrap().is_active);
+    }
+
+    #[test]
+    fn test_reap_stale_past_end() {
+        let mut contract = setup_contract();
+        register_test_coordinator(&mut contract);
+        register_test_worker(&mut contract);
+
+        let result = contract.reap_stale(Some(5), None);
+        assert_eq!(result.deactivated, 0);
+        assert_eq!(result.next_index, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "reap_after must be >= staleness_window")]
+    fn test_set_liveness_config_invalid() {
+        let mut contract = setup_contract();
+        contract.set_liveness_config(1_000, 100);
+    }
+
     // ========== STATS ==========
 
     #[test]
@@ -19,6 +19,8 @@ export interface WorkerResult {
   output: {
     value: number;
     vote?: VoteDecision;
+    /** Options the worker supports, most preferred first (multi-option proposals) */
+    ranking?: string[];
     reasoning?: string;
     data?: any;
     computedAt: string;
@@ -26,6 +28,30 @@ export interface WorkerResult {
   processingTime?: number;
 }
 
+/**
+ * How the winning option of a multi-option proposal is chosen
+ * (matches contract ResolutionMode enum)
+ */
+export type ResolutionMode = 'Plurality' | 'Approval' | 'InstantRunoff';
+
+/**
+ * Ranked ballots that share the same ranking, with their combined weight
+ */
+export interface RankedBallot {
+  ranking: string[];
+  weight: number;
+}
+
+/**
+ * Tally of a multi-option proposal (matches contract OptionTally):
+ * weight per option for plurality and approval, ranked ballots for instant-runoff
+ */
+export interface OptionTally {
+  tally: Record<string, number>;
+  ballots: RankedBallot[];
+  winner: string | null;
+}
+
 /**
  * Vote tally from coordinator (full detail, stored in Ensue only)
  */
@@ -40,6 +66,14 @@ export interface TallyResult {
   proposalId?: number;
   /** DID → display name map for all participating workers */
   workerNames?: Record<string, string>;
+  /** Set when the proposal declares options */
+  optionTally?: OptionTally;
+  /** Weight of the workers that took part without taking a side */
+  abstained: number;
+  /** Weight of the workers that voted or abstained */
+  participation: number;
+  /** Whether participation reached the proposal's quorum (contract refuses to settle otherwise) */
+  quorumReached: boolean;
 }
 
 /**
@@ -50,10 +84,15 @@ export interface OnChainResult {
   aggregatedValue: number;
   approved: number;
   rejected: number;
+  abstained: number;
   decision: VoteDecision;
   workerCount: number;
   timestamp: string;
   proposalId: number;
+  /** Multi-option proposals only, see OptionTally */
+  tally?: Record<string, number>;
+  ballots?: RankedBallot[];
+  winner?: string | null;
 }
 
 /**
@@ -70,6 +109,12 @@ export interface TaskConfig {
  */
 export type ProposalState = 'Created' | 'WorkersCompleted' | 'Finalized' | 'TimedOut';
 
+/**
+ * Outcome of a worker's participation (matches contract SubmissionStatus enum):
+ * abstentions count toward participation only, errored workers toward neither
+ */
+export type SubmissionStatus = 'Voted' | 'Abstained' | 'Errored';
+
 /**
  * Worker submission recorded on-chain (nullifier only — no vote data).
  * Individual votes stay private in Ensue shared memory.
@@ -78,6 +123,7 @@ export interface WorkerSubmission {
   worker_id: string;
   result_hash: string;
   timestamp: number;
+  status: SubmissionStatus;
 }
 
 /**
@@ -86,6 +132,7 @@ export interface WorkerSubmission {
 export interface WorkerSubmissionInput {
   worker_id: string;
   result_hash: string;
+  status: SubmissionStatus;
 }
 
 /**
@@ -42,6 +42,12 @@ console.log(`Storacha configured: ${process.env.STORACHA_AGENT_PRIVATE_KEY ? 'YE
 console.log(`Registry: ${process.env.REGISTRY_CONTRACT_ID || 'not configured'}`);
 console.log(`Coordinator DID: ${process.env.COORDINATOR_DID || 'not set'}`);
 
+// The registry only lists workers with an approved codehash, so an empty one can never register
+if (process.env.REGISTRY_CONTRACT_ID && !process.env.APP_CODEHASH) {
+  console.error('APP_CODEHASH is not set — it is required to register in the registry');
+  process.exit(1);
+}
+
 serve({ fetch: app.fetch, port }, async (info) => {
   console.log(`Worker Agent running at http://localhost:${info.port}`);
   // initializeWorker() derives DID, sets idle status, and self-registers in registry
@@ -19,10 +19,17 @@ const SYSTEM_MESSAGE =
   'You must keep responses under 10,000 characters.';
 
 export interface AiVoteResult extends VoteResult {
+  /** Options the agent supports, most preferred first (multi-option proposals) */
+  ranking?: string[];
   verificationProof?: VerificationProof;
 }
 
-export async function aiVote(manifesto: string, proposal: string, agentContext?: string): Promise<AiVoteResult> {
+export async function aiVote(
+  manifesto: string,
+  proposal: string,
+  agentContext?: string,
+  options?: string[],
+): Promise<AiVoteResult> {
   const apiKey = process.env.NEAR_AI_API_KEY || process.env.NEAR_API_KEY;
   if (!apiKey) {
     throw new Error('NEAR_AI_API_KEY or NEAR_API_KEY environment variable is not set');
@@ -38,6 +45,10 @@ export async function aiVote(manifesto: string, proposal: string, agentContext?:
     userMessage += `${agentContext}\n\n`;
   }
   userMessage += `=== DAO MANIFESTO ===\n${manifesto}\n\n=== PROPOSAL ===\n${proposal}`;
+  if (options) {
+    userMessage += `\n\n=== OPTIONS ===\n${options.map(o => `- ${o}`).join('\n')}\n\n` +
+      'Rank the options you support, most preferred first. Leave out options you oppose.';
+  }
 
   const model = 'deepseek-ai/DeepSeek-V3.1';
 
@@ -53,12 +64,19 @@ export async function aiVote(manifesto: string, proposal: string, agentContext?:
             type: 'object',
             properties: {
               vote: { type: 'string', enum: ['Approved', 'Rejected'] },
+              ...(options && {
+                ranking: {
+                  type: 'array',
+                  items: { type: 'string', enum: options },
+                  description: 'Options you support, most preferred first',
+                },
+              }),
               reasoning: {
                 type: 'string',
                 description: 'Explanation for the voting decision based on the manifesto',
               },
             },
-            required: ['vote', 'reasoning'],
+            required: options ? ['vote', 'ranking', 'reasoning'] : ['vote', 'reasoning'],
           },
         },
       },
@@ -83,6 +101,11 @@ export async function aiVote(manifesto: string, proposal: string, agentContext?:
     throw new Error(`Invalid vote: "${rawResponse.vote}". Must be "Approved" or "Rejected"`);
   }
 
+  if (options && (!Array.isArray(rawResponse.ranking) ||
+      rawResponse.ranking.some((o: unknown) => !options.includes(o as string)))) {
+    throw new Error(`Invalid ranking: ${JSON.stringify(rawResponse.ranking)}. Must list declared options`);
+  }
+
   if (rawResponse.reasoning.length > 10000) {
     throw new Error(`AI reasoning too long: ${rawResponse.reasoning.length} chars`);
   }
@@ -110,6 +133,7 @@ export async function aiVote(manifesto: string, proposal: string, agentContext?:
 
   return {
     vote: rawResponse.vote,
+    ranking: options ? rawResponse.ranking : undefined,
     reasoning: rawResponse.reasoning,
     verificationProof,
   };
@@ -124,6 +124,7 @@ async function ensureRegistered(): Promise<void> {
 
     const endpointUrl = process.env.WORKER_ENDPOINT_URL ?? `http://localhost:${process.env.PORT ?? '3001'}`;
     const cvmId = process.env.PHALA_CVM_ID ?? 'local';
+    const codehash = process.env.APP_CODEHASH as string; // checked at startup
 
     const account = await getNearAccount();
     await account.functionCall({
@@ -134,6 +135,7 @@ async function ensureRegistered(): Promise<void> {
         worker_did: workerDID,
         endpoint_url: endpointUrl,
         cvm_id: cvmId,
+        codehash,
       },
       gas: BigInt(GAS_200T),
       attachedDeposit: BigInt(DEPOSIT_0_1_NEAR),
@@ -178,6 +180,7 @@ export async function executeTask(taskConfig: TaskConfig): Promise<void> {
       output: {
         value: result.value,
         vote: result.vote,
+        ranking: result.ranking,
         reasoning: result.reasoning,
         data: {
           parameters: taskConfig.parameters,
@@ -218,6 +221,7 @@ export async function executeTask(taskConfig: TaskConfig): Promise<void> {
 interface WorkResult {
   value: number;
   vote?: 'Approved' | 'Rejected';
+  ranking?: string[];
   reasoning?: string;
   verificationProof?: VerificationProof;
 }
@@ -265,8 +269,12 @@ async function performWork(config: TaskConfig): Promise<WorkResult> {
       }
 
       console.log(`[worker] Calling AI for vote on proposal...`);
-      const voteResult: AiVoteResult = await aiVote(manifesto.text, proposal, agentContext);
+      const options = config.parameters?.options as string[] | undefined;
+      const voteResult: AiVoteResult = await aiVote(manifesto.text, proposal, agentContext, options);
       console.log(`[worker] AI vote: ${voteResult.vote}`);
+      if (voteResult.ranking) {
+        console.log(`[worker] AI ranking: ${voteResult.ranking.join(' > ')}`);
+      }
       console.log(`[worker] AI reasoning: ${voteResult.reasoning.substring(0, 200)}...`);
 
       if (voteResult.verificationProof) {
@@ -299,6 +307,7 @@ async function performWork(config: TaskConfig): Promise<WorkResult> {
       return {
         value: voteResult.vote === 'Approved' ? 1 : 0,
         vote: voteResult.vote,
+        ranking: voteResult.ranking,
         reasoning: voteResult.reasoning,
         verificationProof: voteResult.verificationProof,
       };